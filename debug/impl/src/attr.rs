@@ -0,0 +1,806 @@
+use crate::format::FieldFormat;
+use quote::ToTokens;
+
+const DEFAULT_REDACTION: &str = "[REDACTED]";
+
+const CONTAINER_KEYS: &[&str] = &[
+    "bound",
+    "phantom",
+    "transparent",
+    "mark_skipped",
+    "alt_name",
+    "fields",
+    "name",
+    "rename_all",
+];
+const VARIANT_KEYS: &[&str] = &["rename", "rename_all"];
+const FIELD_KEYS: &[&str] = &[
+    "skip", "skip_if", "redact", "with", "bound", "flatten", "limit", "hex", "len_only", "alt",
+    "alt_with", "rename",
+];
+
+// The case conventions of `rename_all`, named as in serde.
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+const RENAME_RULES: &[(&str, RenameRule)] = &[
+    ("lowercase", RenameRule::Lower),
+    ("UPPERCASE", RenameRule::Upper),
+    ("PascalCase", RenameRule::Pascal),
+    ("camelCase", RenameRule::Camel),
+    ("snake_case", RenameRule::Snake),
+    ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+    ("kebab-case", RenameRule::Kebab),
+    ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebab),
+];
+
+impl RenameRule {
+    fn parse(lit_str: &syn::LitStr) -> syn::Result<Self> {
+        let value = lit_str.value();
+        match RENAME_RULES.iter().find(|(name, _)| *name == value) {
+            Some((_, rule)) => Ok(*rule),
+            None => Err(syn::Error::new_spanned(
+                lit_str,
+                format!(
+                    "unknown rename rule `{}`, expected one of {}",
+                    value,
+                    RENAME_RULES
+                        .iter()
+                        .map(|(name, _)| format!("`{}`", name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            )),
+        }
+    }
+
+    // Field names are taken to be in snake_case.
+    pub(crate) fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_string(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut res = String::new();
+                for word in field.split('_') {
+                    let mut chars = word.chars();
+                    if let Some(first) = chars.next() {
+                        res.extend(first.to_uppercase());
+                        res.push_str(chars.as_str());
+                    }
+                }
+                res
+            }
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply_to_field(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_lowercase().chain(chars).collect(),
+                    None => pascal,
+                }
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+
+    // Variant names are taken to be in PascalCase.
+    pub(crate) fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::Pascal => variant.to_string(),
+            RenameRule::Lower => variant.to_ascii_lowercase(),
+            RenameRule::Upper => variant.to_ascii_uppercase(),
+            RenameRule::Camel => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    Some(first) => first.to_lowercase().chain(chars).collect(),
+                    None => variant.to_string(),
+                }
+            }
+            _ => {
+                let mut snake = String::new();
+                for (i, c) in variant.char_indices() {
+                    if i > 0 && c.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.extend(c.to_lowercase());
+                }
+                self.apply_to_field(&snake)
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ContainerAttributes {
+    pub(crate) format: Option<syn::LitStr>,
+    pub(crate) bound: Option<Vec<syn::WherePredicate>>,
+    pub(crate) phantom: Vec<String>,
+    pub(crate) transparent: bool,
+    pub(crate) mark_skipped: bool,
+    pub(crate) alt_name: Option<String>,
+    pub(crate) fields: bool,
+    pub(crate) name: Option<String>,
+    pub(crate) rename_all: Option<RenameRule>,
+}
+
+#[derive(Default)]
+pub(crate) struct VariantAttributes {
+    pub(crate) rename: Option<String>,
+    pub(crate) rename_all: Option<RenameRule>,
+}
+
+#[derive(Default)]
+pub(crate) struct FieldAttributes {
+    pub(crate) format: Option<FieldFormat>,
+    pub(crate) skip: bool,
+    pub(crate) skip_if: Option<syn::ExprPath>,
+    pub(crate) redact: Option<String>,
+    pub(crate) with: Option<syn::ExprPath>,
+    pub(crate) bound: Option<Vec<syn::WherePredicate>>,
+    pub(crate) flatten: bool,
+    pub(crate) limit: Option<usize>,
+    pub(crate) hex: bool,
+    pub(crate) len_only: bool,
+    pub(crate) alt: Option<FieldFormat>,
+    pub(crate) alt_with: Option<syn::ExprPath>,
+    pub(crate) rename: Option<String>,
+}
+
+impl ContainerAttributes {
+    fn parse(
+        st: &syn::DeriveInput,
+        fields: &[(&syn::Field, FieldAttributes)],
+    ) -> syn::Result<Self> {
+        let mut errors = Errors::default();
+        let mut res = ContainerAttributes {
+            phantom: vec!["PhantomData".to_string()],
+            ..Default::default()
+        };
+        let (format, entries) = get_entries(&st.attrs, CONTAINER_KEYS, &mut errors);
+        // The `name` and `alt_name` entries, which replace the type name.
+        let mut names = vec![];
+        if let Some(format) = format {
+            if let syn::Data::Union(_) = st.data {
+                res.format = Some(format);
+            } else {
+                errors.push(syn::Error::new_spanned(
+                    format,
+                    "a format string on the type itself is only supported for unions",
+                ));
+            }
+        }
+        for entry in entries {
+            match entry.key.as_str() {
+                "bound" => {
+                    if let Some(lit_str) = entry.string(&mut errors) {
+                        match parse_bounds(&lit_str) {
+                            Ok(bounds) => res.bound.get_or_insert_with(Vec::new).extend(bounds),
+                            Err(e) => errors.push(e),
+                        }
+                    }
+                }
+                "phantom" => {
+                    if let Some(lit_str) = entry.string(&mut errors) {
+                        let paths = lit_str.parse_with(
+                            syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+                        );
+                        match paths {
+                            Ok(paths) => res.phantom.extend(
+                                paths
+                                    .iter()
+                                    .filter_map(|path| path.segments.last())
+                                    .map(|seg| seg.ident.to_string()),
+                            ),
+                            Err(e) => errors.push(e),
+                        }
+                    }
+                }
+                "transparent" => {
+                    if entry.flag(&mut errors) {
+                        res.transparent = true;
+                        check_transparent(st, fields, &entry.path, &mut errors);
+                    }
+                }
+                "mark_skipped" => {
+                    if entry.flag(&mut errors) {
+                        res.mark_skipped = true;
+                    }
+                }
+                "fields" => {
+                    if entry.flag(&mut errors) {
+                        res.fields = true;
+                    }
+                }
+                "alt_name" | "name" => {
+                    if let Some(lit_str) = entry.string(&mut errors) {
+                        if let syn::Data::Enum(_) = st.data {
+                            errors.push(syn::Error::new_spanned(
+                                &entry.path,
+                                format!("`{}` is not supported on enums", entry.key),
+                            ));
+                        }
+                        if entry.key == "name" {
+                            res.name = Some(lit_str.value());
+                        } else {
+                            res.alt_name = Some(lit_str.value());
+                        }
+                        names.push(entry);
+                    }
+                }
+                "rename_all" => {
+                    if let Some(lit_str) = entry.string(&mut errors) {
+                        match RenameRule::parse(&lit_str) {
+                            Ok(rule) => res.rename_all = Some(rule),
+                            Err(e) => errors.push(e),
+                        }
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+        if res.transparent {
+            for entry in names {
+                errors.push(syn::Error::new_spanned(
+                    entry.path,
+                    format!("`{}` cannot be combined with `transparent`", entry.key),
+                ));
+            }
+        }
+        errors.finish(res)
+    }
+}
+
+fn check_transparent(
+    st: &syn::DeriveInput,
+    fields: &[(&syn::Field, FieldAttributes)],
+    path: &syn::Path,
+    errors: &mut Errors,
+) {
+    let field = match &st.data {
+        syn::Data::Struct(syn::DataStruct { fields, .. }) if fields.len() == 1 => {
+            fields.iter().next().unwrap()
+        }
+        _ => {
+            errors.push(syn::Error::new_spanned(
+                path,
+                "`transparent` requires a struct with exactly one field",
+            ));
+            return;
+        }
+    };
+    if let Some((_, arrtibures)) = fields.iter().find(|(item, _)| std::ptr::eq(*item, field)) {
+        if arrtibures.skip || arrtibures.skip_if.is_some() || arrtibures.flatten {
+            errors.push(syn::Error::new_spanned(
+                path,
+                "the field of a `transparent` struct cannot be skipped or flattened",
+            ));
+        }
+    }
+}
+
+impl VariantAttributes {
+    fn parse(variant: &syn::Variant) -> syn::Result<Self> {
+        let mut errors = Errors::default();
+        let mut res = VariantAttributes::default();
+        let (format, entries) = get_entries(&variant.attrs, VARIANT_KEYS, &mut errors);
+        if let Some(format) = format {
+            errors.push(syn::Error::new_spanned(
+                format,
+                "a format string is not supported on enum variants",
+            ));
+        }
+        for entry in entries {
+            if let Some(lit_str) = entry.string(&mut errors) {
+                match entry.key.as_str() {
+                    "rename" => res.rename = Some(lit_str.value()),
+                    "rename_all" => match RenameRule::parse(&lit_str) {
+                        Ok(rule) => res.rename_all = Some(rule),
+                        Err(e) => errors.push(e),
+                    },
+                    _ => unreachable!(),
+                }
+            }
+        }
+        errors.finish(res)
+    }
+}
+
+impl FieldAttributes {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut errors = Errors::default();
+        let mut res = FieldAttributes::default();
+        let (format, entries) = get_entries(&field.attrs, FIELD_KEYS, &mut errors);
+        // Every way of rendering the field, as (description, span); at most
+        // one of them may be used, except that `hex` combines with `limit`.
+        let mut renderers: Vec<(String, proc_macro2::TokenStream)> = vec![];
+        if let Some(format) = &format {
+            match FieldFormat::parse(format) {
+                Ok(format) => res.format = Some(format),
+                Err(e) => errors.push(e),
+            }
+            renderers.push(("a format string".to_string(), format.to_token_stream()));
+        }
+        // The same for the `{:#?}` rendering, which replaces the one above.
+        let mut alt_renderers: Vec<(String, proc_macro2::TokenStream)> = vec![];
+        let mut skip = None;
+        let mut skip_if = None;
+        for entry in entries {
+            let renderer = (format!("`{}`", entry.key), entry.path.to_token_stream());
+            match entry.key.as_str() {
+                "skip" => {
+                    if entry.flag(&mut errors) {
+                        res.skip = true;
+                        skip = Some(entry.path);
+                        renderers.push(renderer);
+                    }
+                }
+                "skip_if" => {
+                    if let Some(lit_str) = entry.string(&mut errors) {
+                        match lit_str.parse() {
+                            Ok(path) => res.skip_if = Some(path),
+                            Err(e) => errors.push(e),
+                        }
+                        skip_if = Some(entry.path);
+                    }
+                }
+                "redact" => {
+                    if entry.value.is_none() {
+                        res.redact = Some(DEFAULT_REDACTION.to_string());
+                        renderers.push(renderer);
+                    } else if let Some(lit_str) = entry.string(&mut errors) {
+                        res.redact = Some(lit_str.value());
+                        renderers.push(renderer);
+                    }
+                }
+                "with" => {
+                    if let Some(lit_str) = entry.string(&mut errors) {
+                        match lit_str.parse() {
+                            Ok(path) => res.with = Some(path),
+                            Err(e) => errors.push(e),
+                        }
+                        renderers.push(renderer);
+                    }
+                }
+                "bound" => {
+                    if let Some(lit_str) = entry.string(&mut errors) {
+                        match parse_bounds(&lit_str) {
+                            Ok(bounds) => res.bound.get_or_insert_with(Vec::new).extend(bounds),
+                            Err(e) => errors.push(e),
+                        }
+                    }
+                }
+                "flatten" => {
+                    if entry.flag(&mut errors) {
+                        if field.ident.is_none() {
+                            errors.push(syn::Error::new_spanned(
+                                &entry.path,
+                                "`flatten` is only supported on named fields",
+                            ));
+                        }
+                        res.flatten = true;
+                        renderers.push(renderer);
+                    }
+                }
+                "limit" => {
+                    if let Some(limit) = entry.int(&mut errors) {
+                        res.limit = Some(limit);
+                        if !res.hex {
+                            renderers.push(renderer);
+                        }
+                    }
+                }
+                "hex" => {
+                    if entry.flag(&mut errors) {
+                        res.hex = true;
+                        if res.limit.is_none() {
+                            renderers.push(renderer);
+                        }
+                    }
+                }
+                "len_only" => {
+                    if entry.flag(&mut errors) {
+                        res.len_only = true;
+                        renderers.push(renderer);
+                    }
+                }
+                "alt" => {
+                    if let Some(lit_str) = entry.string(&mut errors) {
+                        match FieldFormat::parse(&lit_str) {
+                            Ok(format) => res.alt = Some(format),
+                            Err(e) => errors.push(e),
+                        }
+                        alt_renderers.push(renderer);
+                    }
+                }
+                "alt_with" => {
+                    if let Some(lit_str) = entry.string(&mut errors) {
+                        match lit_str.parse() {
+                            Ok(path) => res.alt_with = Some(path),
+                            Err(e) => errors.push(e),
+                        }
+                        alt_renderers.push(renderer);
+                    }
+                }
+                "rename" => {
+                    if let Some(lit_str) = entry.string(&mut errors) {
+                        if field.ident.is_none() {
+                            errors.push(syn::Error::new_spanned(
+                                &entry.path,
+                                "`rename` is only supported on named fields",
+                            ));
+                        }
+                        res.rename = Some(lit_str.value());
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+        if let (Some(skip_if), Some(_)) = (&skip_if, &skip) {
+            errors.push(syn::Error::new_spanned(
+                skip_if,
+                "`skip_if` cannot be combined with `skip`",
+            ));
+        }
+        if let [(first, _), (second, tokens), ..] = renderers.as_slice() {
+            errors.push(syn::Error::new_spanned(
+                tokens,
+                format!("{} cannot be combined with {}", second, first),
+            ));
+        }
+        if let [(first, _), (second, tokens), ..] = alt_renderers.as_slice() {
+            errors.push(syn::Error::new_spanned(
+                tokens,
+                format!("{} cannot be combined with {}", second, first),
+            ));
+        }
+        if let Some((alt, tokens)) = alt_renderers.first() {
+            let hidden = renderers
+                .iter()
+                .map(|(first, _)| first.as_str())
+                .find(|first| ["`skip`", "`redact`", "`flatten`"].contains(first));
+            if let Some(first) = hidden {
+                errors.push(syn::Error::new_spanned(
+                    tokens,
+                    format!("{} cannot be combined with {}", alt, first),
+                ));
+            }
+        }
+        errors.finish(res)
+    }
+
+    pub(crate) fn is_read(&self) -> bool {
+        !self.skip && self.redact.is_none()
+    }
+
+    pub(crate) fn needs_bound(&self) -> bool {
+        self.is_read()
+            && !self.flatten
+            && !self.hex
+            && !self.len_only
+            && self.with.is_none()
+            && self.bound.is_none()
+    }
+}
+
+// Every debug attribute of the input, parsed once up front so that all
+// mistakes are reported together instead of one per compilation.
+pub(crate) struct Attributes<'a> {
+    pub(crate) container: ContainerAttributes,
+    variants: Vec<(&'a syn::Variant, VariantAttributes)>,
+    // Union fields are never printed, so only those of structs and enums.
+    fields: Vec<(&'a syn::Field, FieldAttributes)>,
+}
+
+impl<'a> Attributes<'a> {
+    pub(crate) fn parse(st: &'a syn::DeriveInput) -> syn::Result<Self> {
+        // Errors in variants and fields, reported after those of the container.
+        let mut field_errors = Errors::default();
+        let mut variants = vec![];
+        let mut fields = vec![];
+        match &st.data {
+            syn::Data::Struct(syn::DataStruct { fields: items, .. }) => {
+                for item in items {
+                    if let Some(arrtibures) = field_errors.ok(FieldAttributes::parse(item)) {
+                        fields.push((item, arrtibures));
+                    }
+                }
+            }
+            syn::Data::Enum(syn::DataEnum {
+                variants: items, ..
+            }) => {
+                for variant in items {
+                    if let Some(arrtibures) = field_errors.ok(VariantAttributes::parse(variant)) {
+                        variants.push((variant, arrtibures));
+                    }
+                    for item in &variant.fields {
+                        if let Some(arrtibures) = field_errors.ok(FieldAttributes::parse(item)) {
+                            fields.push((item, arrtibures));
+                        }
+                    }
+                }
+            }
+            syn::Data::Union(syn::DataUnion { fields: items, .. }) => {
+                for item in &items.named {
+                    for attr in &item.attrs {
+                        if attr.path.is_ident("debug") {
+                            field_errors.push(syn::Error::new_spanned(
+                                attr,
+                                "union fields are never printed, so they take no debug attributes",
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        let mut errors = Errors::default();
+        let container = errors.ok(ContainerAttributes::parse(st, &fields));
+        if let Some(e) = field_errors.error {
+            errors.push(e);
+        }
+        errors.finish(())?;
+        Ok(Attributes {
+            container: container.unwrap(),
+            variants,
+            fields,
+        })
+    }
+
+    pub(crate) fn field(&self, field: &syn::Field) -> &FieldAttributes {
+        self.fields
+            .iter()
+            .find(|(item, _)| std::ptr::eq(*item, field))
+            .map(|(_, arrtibures)| arrtibures)
+            .expect("a field of the input")
+    }
+
+    pub(crate) fn variant(&self, variant: &syn::Variant) -> &VariantAttributes {
+        self.variants
+            .iter()
+            .find(|(item, _)| std::ptr::eq(*item, variant))
+            .map(|(_, arrtibures)| arrtibures)
+            .expect("a variant of the input")
+    }
+}
+
+type WherePredicates = syn::punctuated::Punctuated<syn::WherePredicate, syn::Token![,]>;
+
+fn parse_bounds(lit_str: &syn::LitStr) -> syn::Result<Vec<syn::WherePredicate>> {
+    let predicates = lit_str.parse_with(WherePredicates::parse_terminated)?;
+    Ok(predicates.into_iter().collect())
+}
+
+#[derive(Default)]
+struct Errors {
+    error: Option<syn::Error>,
+}
+
+impl Errors {
+    fn push(&mut self, error: syn::Error) {
+        match &mut self.error {
+            Some(res) => res.combine(error),
+            None => self.error = Some(error),
+        }
+    }
+
+    fn ok<T>(&mut self, res: syn::Result<T>) -> Option<T> {
+        match res {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.push(e);
+                None
+            }
+        }
+    }
+
+    fn finish<T>(self, value: T) -> syn::Result<T> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(value),
+        }
+    }
+}
+
+struct Entry {
+    key: String,
+    path: syn::Path,
+    value: Option<syn::Lit>,
+}
+
+impl Entry {
+    fn flag(&self, errors: &mut Errors) -> bool {
+        match &self.value {
+            Some(lit) => {
+                errors.push(syn::Error::new_spanned(
+                    lit,
+                    format!("`{}` does not take a value", self.key),
+                ));
+                false
+            }
+            None => true,
+        }
+    }
+
+    fn int(&self, errors: &mut Errors) -> Option<usize> {
+        match &self.value {
+            Some(syn::Lit::Int(lit_int)) => match lit_int.base10_parse() {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    errors.push(e);
+                    None
+                }
+            },
+            Some(lit) => {
+                errors.push(syn::Error::new_spanned(
+                    lit,
+                    format!("expected `{} = <integer>`", self.key),
+                ));
+                None
+            }
+            None => {
+                errors.push(syn::Error::new_spanned(
+                    &self.path,
+                    format!("expected `{} = <integer>`", self.key),
+                ));
+                None
+            }
+        }
+    }
+
+    fn string(&self, errors: &mut Errors) -> Option<syn::LitStr> {
+        match &self.value {
+            Some(syn::Lit::Str(lit_str)) => Some(lit_str.clone()),
+            Some(lit) => {
+                errors.push(syn::Error::new_spanned(
+                    lit,
+                    format!("expected `{} = \"...\"`", self.key),
+                ));
+                None
+            }
+            None => {
+                errors.push(syn::Error::new_spanned(
+                    &self.path,
+                    format!("expected `{} = \"...\"`", self.key),
+                ));
+                None
+            }
+        }
+    }
+}
+
+// Splits the `debug` attributes into an optional `#[debug = "..."]` format
+// string and the `key` / `key = value` entries of `#[debug(...)]`, keeping
+// only entries whose key is known. Attributes other than `debug` are ignored.
+fn get_entries(
+    attrs: &[syn::Attribute],
+    known: &[&str],
+    errors: &mut Errors,
+) -> (Option<syn::LitStr>, Vec<Entry>) {
+    let mut format: Option<syn::LitStr> = None;
+    let mut entries: Vec<Entry> = vec![];
+    for attr in attrs {
+        if !attr.path.is_ident("debug") {
+            continue;
+        }
+        let meta = match attr.parse_meta() {
+            Ok(meta) => meta,
+            Err(e) => {
+                errors.push(e);
+                continue;
+            }
+        };
+        let nested = match meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(lit_str),
+                ..
+            }) => {
+                if format.is_some() {
+                    errors.push(syn::Error::new_spanned(lit_str, "duplicate format string"));
+                } else {
+                    format = Some(lit_str);
+                }
+                continue;
+            }
+            syn::Meta::NameValue(syn::MetaNameValue { lit, .. }) => {
+                errors.push(syn::Error::new_spanned(
+                    lit,
+                    "expected a format string literal",
+                ));
+                continue;
+            }
+            syn::Meta::Path(path) => {
+                errors.push(syn::Error::new_spanned(
+                    path,
+                    "expected `#[debug = \"...\"]` or `#[debug(...)]`",
+                ));
+                continue;
+            }
+            syn::Meta::List(syn::MetaList { nested, .. }) => nested,
+        };
+        for item in nested {
+            let (path, value) = match item {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) => (path, None),
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit,
+                    ..
+                })) => (path, Some(lit)),
+                syn::NestedMeta::Meta(syn::Meta::List(list)) => {
+                    errors.push(syn::Error::new_spanned(
+                        list,
+                        "expected `key` or `key = \"...\"`",
+                    ));
+                    continue;
+                }
+                syn::NestedMeta::Lit(lit) => {
+                    errors.push(syn::Error::new_spanned(
+                        lit,
+                        "expected `key` or `key = \"...\"`",
+                    ));
+                    continue;
+                }
+            };
+            let key = path.to_token_stream().to_string();
+            if !known.contains(&key.as_str()) {
+                errors.push(unknown_key(&path, &key, known));
+                continue;
+            }
+            if key != "bound" && entries.iter().any(|entry| entry.key == key) {
+                errors.push(syn::Error::new_spanned(
+                    path,
+                    format!("duplicate `{}` attribute", key),
+                ));
+                continue;
+            }
+            entries.push(Entry { key, path, value });
+        }
+    }
+    (format, entries)
+}
+
+fn unknown_key(path: &syn::Path, key: &str, known: &[&str]) -> syn::Error {
+    let suggestion = known
+        .iter()
+        .map(|item| (edit_distance(key, item), item))
+        .filter(|(distance, _)| *distance <= 2 && *distance < key.len())
+        .min();
+    let message = match (suggestion, known.is_empty()) {
+        (Some((_, item)), _) => format!(
+            "unknown debug attribute `{}`, did you mean `{}`?",
+            key, item
+        ),
+        (None, true) => format!("unknown debug attribute `{}`, none are supported here", key),
+        (None, false) => format!(
+            "unknown debug attribute `{}`, expected one of {}",
+            key,
+            known
+                .iter()
+                .map(|item| format!("`{}`", item))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    syn::Error::new_spanned(path, message)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            cur.push((prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1));
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
@@ -0,0 +1,201 @@
+// `#[derive(CustomDisplay)]`: a Display impl built from a template such as
+// `#[display("{name} listening on {addr}:{port}")]`, given on a struct or on
+// each variant of an enum. Placeholders name a field (or index one, in tuple
+// structs and variants) and may carry any format spec; each referenced field
+// type is bounded by the trait its spec asks for.
+
+use crate::format::{format_trait_bound, rewrite_placeholders};
+use syn::ext::IdentExt;
+
+struct Template {
+    lit: syn::LitStr,
+    // Index of the field behind each positional argument of `lit`.
+    args: Vec<usize>,
+    // Every (field index, format trait) pair used by the template.
+    uses: Vec<(usize, String)>,
+}
+
+impl Template {
+    fn parse(lit: &syn::LitStr, fields: &syn::Fields, name: &syn::Ident) -> syn::Result<Self> {
+        let mut args: Vec<usize> = vec![];
+        let mut uses = vec![];
+        let lit = rewrite_placeholders(lit, |arg, format_trait| {
+            if arg.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "placeholders must name a field, like `{field}` or `{0}`",
+                ));
+            }
+            let field = fields
+                .iter()
+                .enumerate()
+                .position(|(i, item)| match &item.ident {
+                    Some(ident) => ident.unraw() == arg,
+                    None => i.to_string() == arg,
+                });
+            let field = field.ok_or_else(|| {
+                syn::Error::new_spanned(lit, format!("no field `{}` on `{}`", arg, name))
+            })?;
+            uses.push((field, format_trait.to_string()));
+            match args.iter().position(|item| *item == field) {
+                Some(i) => Ok(i),
+                None => {
+                    args.push(field);
+                    Ok(args.len() - 1)
+                }
+            }
+        })?;
+        Ok(Self { lit, args, uses })
+    }
+
+    fn write(&self, values: &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream {
+        let lit = &self.lit;
+        let args = self.args.iter().map(|i| &values[*i]);
+        quote::quote!(f.write_fmt(::core::format_args!(#lit #(, #args)*)))
+    }
+}
+
+fn get_template(attrs: &[syn::Attribute]) -> syn::Result<Option<syn::LitStr>> {
+    let mut res = None;
+    for attr in attrs {
+        if !attr.path.is_ident("display") {
+            continue;
+        }
+        let lit: syn::LitStr = attr
+            .parse_args()
+            .map_err(|_| syn::Error::new_spanned(attr, "expected `#[display(\"...\")]`"))?;
+        if res.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "duplicate `display` attribute",
+            ));
+        }
+        res = Some(lit);
+    }
+    Ok(res)
+}
+
+fn get_required_template(attrs: &[syn::Attribute], name: &syn::Ident) -> syn::Result<syn::LitStr> {
+    get_template(attrs)?
+        .ok_or_else(|| syn::Error::new_spanned(name, "missing `#[display(\"...\")]` attribute"))
+}
+
+fn display_variant<'a>(
+    variant: &'a syn::Variant,
+    used: &mut Vec<(&'a syn::Field, String)>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let variant_name = &variant.ident;
+    let template = get_required_template(&variant.attrs, variant_name)?;
+    let template = Template::parse(&template, &variant.fields, variant_name)?;
+    let fields: Vec<_> = variant.fields.iter().collect();
+    used.extend(template.uses.iter().map(|(i, t)| (fields[*i], t.clone())));
+    let bindings = crate::get_field_bindings(&variant.fields);
+    let values: Vec<_> = bindings.iter().map(|item| quote::quote!(*#item)).collect();
+    let body = template.write(&values);
+    Ok(match &variant.fields {
+        syn::Fields::Named(syn::FieldsNamed { named, .. }) => {
+            let names = named.iter().map(|item| &item.ident);
+            quote::quote!(Self::#variant_name { #(#names: #bindings),* } => #body,)
+        }
+        syn::Fields::Unnamed(_) => quote::quote!(Self::#variant_name(#(#bindings),*) => #body,),
+        syn::Fields::Unit => quote::quote!(Self::#variant_name => #body,),
+    })
+}
+
+fn display_body<'a>(
+    st: &'a syn::DeriveInput,
+    used: &mut Vec<(&'a syn::Field, String)>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &st.ident;
+    match &st.data {
+        syn::Data::Struct(syn::DataStruct { fields, .. }) => {
+            let template = get_required_template(&st.attrs, name)?;
+            let template = Template::parse(&template, fields, name)?;
+            let all_fields: Vec<_> = fields.iter().collect();
+            used.extend(
+                template
+                    .uses
+                    .iter()
+                    .map(|(i, t)| (all_fields[*i], t.clone())),
+            );
+            let values: Vec<_> = fields
+                .iter()
+                .enumerate()
+                .map(|(i, item)| match &item.ident {
+                    Some(name) => quote::quote!(self.#name),
+                    None => {
+                        let index = syn::Index::from(i);
+                        quote::quote!(self.#index)
+                    }
+                })
+                .collect();
+            Ok(template.write(&values))
+        }
+        syn::Data::Enum(syn::DataEnum { variants, .. }) => {
+            if let Some(template) = get_template(&st.attrs)? {
+                return Err(syn::Error::new_spanned(
+                    template,
+                    "a template on an enum is not supported, put one on each variant",
+                ));
+            }
+            if variants.is_empty() {
+                return Ok(quote::quote!(match *self {}));
+            }
+            let mut arms = vec![];
+            let mut error: Option<syn::Error> = None;
+            for variant in variants {
+                match display_variant(variant, used) {
+                    Ok(arm) => arms.push(arm),
+                    Err(e) => match &mut error {
+                        Some(error) => error.combine(e),
+                        None => error = Some(e),
+                    },
+                }
+            }
+            if let Some(e) = error {
+                return Err(e);
+            }
+            Ok(quote::quote!(
+                match self {
+                    #(#arms)*
+                }
+            ))
+        }
+        syn::Data::Union(syn::DataUnion { fields, .. }) => {
+            let lit = get_required_template(&st.attrs, name)?;
+            let template = Template::parse(&lit, &syn::Fields::Named(fields.clone()), name)?;
+            if !template.args.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "union fields cannot be referenced, as reading them is unsafe",
+                ));
+            }
+            Ok(template.write(&[]))
+        }
+    }
+}
+
+pub(crate) fn expand(st: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let struct_name = crate::get_struct_name(st)?;
+    let mut used = vec![];
+    let body = display_body(st, &mut used)?;
+
+    // Fields are bounded per trait, so that `{count}` and `{mask:x}` put
+    // `T: Display` and `U: LowerHex` on their respective parameters.
+    let phantoms = vec!["PhantomData".to_string()];
+    let used = used
+        .into_iter()
+        .filter_map(|(field, format_trait)| Some((field, format_trait_bound(&format_trait)?)))
+        .collect();
+    let mut generics = st.generics.clone();
+    crate::bound_fields(&mut generics, st, used, &phantoms);
+
+    let (a, b, c) = generics.split_for_impl();
+    Ok(quote::quote!(
+        impl #a ::core::fmt::Display for #struct_name #b #c {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #body
+            }
+        }
+    ))
+}
@@ -0,0 +1,215 @@
+// Validation for the format strings given in `#[debug = "..."]`.
+//
+// The string must contain exactly one placeholder for the field value, written
+// `{}` or `{0}` with an optional format spec. Other fields of the same struct
+// or variant may be referenced as `{self.name}` (or `{self.0}` in tuple
+// structs). The string handed to `format_args!` is rewritten to use explicit
+// positional arguments: 0 for the field value, then one per sibling field.
+
+pub(crate) struct FieldFormat {
+    pub(crate) lit: syn::LitStr,
+    pub(crate) siblings: Vec<syn::Member>,
+    // Every (field, format trait) pair used by the string, where `None` is the
+    // field value itself.
+    pub(crate) uses: Vec<(Option<syn::Member>, String)>,
+}
+
+impl FieldFormat {
+    pub(crate) fn parse(lit: &syn::LitStr) -> syn::Result<Self> {
+        let mut siblings: Vec<syn::Member> = vec![];
+        let mut uses = vec![];
+        let mut placeholders = 0;
+        let res = rewrite_placeholders(lit, |arg, format_trait| {
+            match arg {
+            "" | "0" => {
+                placeholders += 1;
+                uses.push((None, format_trait.to_string()));
+                Ok(0)
+            }
+            _ => match arg.strip_prefix("self.") {
+                Some(member) => {
+                    let member: syn::Member = syn::parse_str(member).map_err(|_| {
+                        syn::Error::new_spanned(lit, format!("invalid field reference `{}`", arg))
+                    })?;
+                    uses.push((Some(member.clone()), format_trait.to_string()));
+                    match siblings.iter().position(|item| *item == member) {
+                        Some(i) => Ok(i + 1),
+                        None => {
+                            siblings.push(member);
+                            Ok(siblings.len())
+                        }
+                    }
+                }
+                None => Err(syn::Error::new_spanned(
+                    lit,
+                    format!(
+                        "unknown argument `{}`, use `{{}}` for the field value or `{{self.field}}` for another field",
+                        arg
+                    ),
+                )),
+            },
+        }
+        })?;
+        if placeholders != 1 {
+            return Err(syn::Error::new_spanned(
+                lit,
+                format!(
+                    "format string must contain exactly one `{{}}` placeholder for the field value, found {}",
+                    placeholders
+                ),
+            ));
+        }
+        Ok(Self {
+            lit: res,
+            siblings,
+            uses,
+        })
+    }
+}
+
+// The trait a value has to implement to be formatted with `format_trait`.
+// Pointer is implemented for references and pointers whatever they point to,
+// so `{:p}` needs no bound.
+pub(crate) fn format_trait_bound(format_trait: &str) -> Option<syn::Path> {
+    Some(match format_trait {
+        "" => syn::parse_quote!(::core::fmt::Display),
+        "?" | "x?" | "X?" => syn::parse_quote!(::core::fmt::Debug),
+        "o" => syn::parse_quote!(::core::fmt::Octal),
+        "x" => syn::parse_quote!(::core::fmt::LowerHex),
+        "X" => syn::parse_quote!(::core::fmt::UpperHex),
+        "b" => syn::parse_quote!(::core::fmt::Binary),
+        "e" => syn::parse_quote!(::core::fmt::LowerExp),
+        "E" => syn::parse_quote!(::core::fmt::UpperExp),
+        _ => return None,
+    })
+}
+
+// Rewrites every `{arg:spec}` placeholder of a format string to `{N:spec}`,
+// where N is what `index` returns for `arg` and the spec's format trait (`""`
+// for Display, `"?"`, `"x"` and so on). Escaped braces are kept as they are.
+pub(crate) fn rewrite_placeholders(
+    lit: &syn::LitStr,
+    mut index: impl FnMut(&str, &str) -> syn::Result<usize>,
+) -> syn::Result<syn::LitStr> {
+    let value = lit.value();
+    let mut chars = value.chars().peekable();
+    let mut res = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                res.push_str("{{");
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "invalid format string: unexpected `{` inside placeholder",
+                            ))
+                        }
+                        Some(c) => placeholder.push(c),
+                        None => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "invalid format string: expected `}` but string was terminated",
+                            ))
+                        }
+                    }
+                }
+                let (arg, spec) = match placeholder.split_once(':') {
+                    Some((arg, spec)) => (arg, Some(spec)),
+                    None => (placeholder.as_str(), None),
+                };
+                let format_trait = match spec {
+                    Some(spec) => validate_spec(lit, spec)?,
+                    None => "",
+                };
+                res.push_str(&format!("{{{}", index(arg, format_trait)?));
+                if let Some(spec) = spec {
+                    res.push(':');
+                    res.push_str(spec);
+                }
+                res.push('}');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                res.push_str("}}");
+            }
+            '}' => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "invalid format string: unmatched `}` found",
+                ))
+            }
+            c => res.push(c),
+        }
+    }
+    Ok(syn::LitStr::new(&res, lit.span()))
+}
+
+const FORMAT_TRAITS: &[&str] = &["", "?", "x?", "X?", "o", "x", "X", "p", "b", "e", "E"];
+
+// format_spec := [[fill]align][sign]['#']['0'][width]['.' precision]type
+// Returns the format trait of the spec.
+fn validate_spec<'a>(lit: &syn::LitStr, spec: &'a str) -> syn::Result<&'a str> {
+    let chars: Vec<char> = spec.chars().collect();
+    let is_align = |c: Option<&char>| matches!(c, Some('<' | '^' | '>'));
+    let mut i = 0;
+    if is_align(chars.get(1)) {
+        i = 2;
+    } else if is_align(chars.first()) {
+        i = 1;
+    }
+    if matches!(chars.get(i), Some('+' | '-')) {
+        i += 1;
+    }
+    if chars.get(i) == Some(&'#') {
+        i += 1;
+    }
+    if chars.get(i) == Some(&'0') {
+        i += 1;
+    }
+    i = skip_count(lit, &chars, i)?;
+    if chars.get(i) == Some(&'.') {
+        if chars.get(i + 1) == Some(&'*') {
+            return Err(syn::Error::new_spanned(
+                lit,
+                "precision taken from an argument (`.*`) is not supported",
+            ));
+        }
+        i = skip_count(lit, &chars, i + 1)?;
+    }
+    let format_trait = &spec[chars[..i].iter().map(|c| c.len_utf8()).sum::<usize>()..];
+    if !FORMAT_TRAITS.contains(&format_trait) {
+        return Err(syn::Error::new_spanned(
+            lit,
+            format!("unknown format trait `{}`", format_trait),
+        ));
+    }
+    Ok(format_trait)
+}
+
+fn skip_count(lit: &syn::LitStr, chars: &[char], start: usize) -> syn::Result<usize> {
+    let mut i = start;
+    while chars.get(i).is_some_and(|c| c.is_ascii_digit()) {
+        i += 1;
+    }
+    let mut end = i;
+    while chars
+        .get(end)
+        .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+    {
+        end += 1;
+    }
+    if chars.get(end) == Some(&'$') {
+        return Err(syn::Error::new_spanned(
+            lit,
+            "width and precision taken from an argument (`name$`) are not supported",
+        ));
+    }
+    Ok(i)
+}
@@ -0,0 +1,884 @@
+use proc_macro::TokenStream;
+use quote::ToTokens;
+use syn::ext::IdentExt;
+use syn::visit::Visit;
+
+mod attr;
+mod display;
+mod format;
+
+#[derive(Debug)]
+struct Visitor {
+    generics: Vec<String>,
+    phantoms: Vec<String>,
+    bounded: Vec<String>,
+    result: Vec<syn::Type>,
+}
+
+impl Visitor {
+    fn new(generics: Vec<String>, phantoms: Vec<String>) -> Self {
+        Self {
+            generics,
+            phantoms,
+            bounded: vec![],
+            result: vec![],
+        }
+    }
+
+    fn is_generic(&self, ident: &syn::Ident) -> bool {
+        self.generics.iter().any(|item| ident == item)
+    }
+
+    fn is_phantom(&self, path: &syn::Path) -> bool {
+        match path.segments.last() {
+            Some(seg) => self.phantoms.iter().any(|item| seg.ident == item),
+            None => false,
+        }
+    }
+
+    fn mentions_generic(&self, ty: &syn::Type) -> bool {
+        let mut mentions = Mentions {
+            generics: &self.generics,
+            found: false,
+        };
+        mentions.visit_type(ty);
+        mentions.found
+    }
+
+    fn push_predicate(&mut self, ty: &syn::Type) {
+        let ty_str = ty.to_token_stream().to_string();
+        if !self
+            .result
+            .iter()
+            .any(|item| item.to_token_stream().to_string() == ty_str)
+        {
+            self.result.push(ty.clone());
+        }
+    }
+}
+
+impl<'ast> syn::visit::Visit<'ast> for Visitor {
+    fn visit_type(&mut self, node: &'ast syn::Type) {
+        match node {
+            syn::Type::Path(syn::TypePath { qself, path }) => {
+                let first = &path.segments[0];
+                if qself.is_some() || (path.segments.len() >= 2 && self.is_generic(&first.ident)) {
+                    // `T::Assoc` or `<T as Trait>::Assoc`: the projection has
+                    // to be Debug, the parameter itself does not.
+                    if self.mentions_generic(node) {
+                        self.push_predicate(node);
+                    }
+                } else if path.segments.len() == 1
+                    && first.arguments.is_empty()
+                    && self.is_generic(&first.ident)
+                {
+                    let ident_str = first.ident.to_string();
+                    if !self.bounded.contains(&ident_str) {
+                        self.bounded.push(ident_str);
+                    }
+                } else if !self.is_phantom(path) {
+                    syn::visit::visit_type(self, node);
+                }
+            }
+            // Raw pointers and fn pointers are Debug whatever they point to.
+            syn::Type::Ptr(_) | syn::Type::BareFn(_) | syn::Type::Never(_) => {}
+            syn::Type::Reference(_)
+            | syn::Type::Slice(_)
+            | syn::Type::Array(_)
+            | syn::Type::Tuple(_)
+            | syn::Type::Paren(_)
+            | syn::Type::Group(_) => syn::visit::visit_type(self, node),
+            // Trait objects, macros and anything else we cannot see through
+            // are bounded as a whole.
+            _ => {
+                if self.mentions_generic(node) {
+                    self.push_predicate(node);
+                }
+            }
+        }
+    }
+}
+
+struct Mentions<'a> {
+    generics: &'a [String],
+    found: bool,
+}
+
+impl<'a, 'ast> syn::visit::Visit<'ast> for Mentions<'a> {
+    fn visit_path(&mut self, node: &'ast syn::Path) {
+        if let Some(seg) = node.segments.first() {
+            if self.generics.iter().any(|item| seg.ident == item) {
+                self.found = true;
+            }
+        }
+        syn::visit::visit_path(self, node);
+    }
+}
+
+#[proc_macro_derive(CustomDebug, attributes(debug))]
+pub fn derive(input: TokenStream) -> TokenStream {
+    let token = syn::parse_macro_input!(input as syn::DeriveInput);
+    match expand(token) {
+        Ok(token_stream) => token_stream.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(CustomDisplay, attributes(display))]
+pub fn derive_display(input: TokenStream) -> TokenStream {
+    let token = syn::parse_macro_input!(input as syn::DeriveInput);
+    match display::expand(&token) {
+        Ok(token_stream) => token_stream.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+fn expand(st: syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = attr::Attributes::parse(&st)?;
+    impl_debug_for_struct(&st, &attrs)
+}
+
+fn get_struct_name(st: &syn::DeriveInput) -> syn::Result<syn::Ident> {
+    Ok(st.ident.clone())
+}
+
+// The name a field is printed under, or `None` for tuple fields.
+fn get_field_name(
+    field: &syn::Field,
+    arrtibures: &attr::FieldAttributes,
+    rename_all: Option<attr::RenameRule>,
+) -> Option<String> {
+    let name = field.ident.as_ref()?.unraw().to_string();
+    Some(match (&arrtibures.rename, rename_all) {
+        (Some(rename), _) => rename.clone(),
+        (None, Some(rule)) => rule.apply_to_field(&name),
+        (None, None) => name,
+    })
+}
+
+fn get_all_fields(st: &syn::DeriveInput) -> Vec<&syn::Field> {
+    match &st.data {
+        syn::Data::Struct(syn::DataStruct { fields, .. }) => fields.iter().collect(),
+        syn::Data::Enum(syn::DataEnum { variants, .. }) => variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter())
+            .collect(),
+        // Union fields are never read by the generated impl.
+        syn::Data::Union(_) => vec![],
+    }
+}
+
+// Every field the impl formats with the trait it needs: Debug by default, or
+// the traits named by the specs of a #[debug = "..."] format, which may also
+// format sibling fields of the same struct or variant.
+fn get_field_uses<'a>(
+    st: &'a syn::DeriveInput,
+    attrs: &attr::Attributes,
+) -> Vec<(&'a syn::Field, syn::Path)> {
+    let groups: Vec<&syn::Fields> = match &st.data {
+        syn::Data::Struct(syn::DataStruct { fields, .. }) => vec![fields],
+        syn::Data::Enum(syn::DataEnum { variants, .. }) => {
+            variants.iter().map(|variant| &variant.fields).collect()
+        }
+        syn::Data::Union(_) => vec![],
+    };
+    let mut res = vec![];
+    for fields in groups {
+        for item in fields {
+            let arrtibures = attrs.field(item);
+            if !arrtibures.is_read() || arrtibures.bound.is_some() {
+                continue;
+            }
+            let format = arrtibures.format.as_ref().filter(|_| arrtibures.with.is_none());
+            if format.is_none() && arrtibures.needs_bound() {
+                res.push((item, syn::parse_quote!(::core::fmt::Debug)));
+            }
+            for format in format.into_iter().chain(&arrtibures.alt) {
+                for (member, format_trait) in &format.uses {
+                    let field = match member {
+                        Some(member) => match get_member_field(fields, member) {
+                            Some(field) => field,
+                            // Reported when the value is generated.
+                            None => continue,
+                        },
+                        None => item,
+                    };
+                    if let Some(bound) = format::format_trait_bound(format_trait) {
+                        res.push((field, bound));
+                    }
+                }
+            }
+        }
+    }
+    res
+}
+
+fn get_member_field<'a>(fields: &'a syn::Fields, member: &syn::Member) -> Option<&'a syn::Field> {
+    fields.iter().enumerate().find_map(|(i, item)| {
+        let item_member = match &item.ident {
+            Some(name) => syn::Member::Named(name.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(i)),
+        };
+        (item_member == *member).then_some(item)
+    })
+}
+
+fn get_field_bindings(fields: &syn::Fields) -> Vec<syn::Ident> {
+    (0..fields.len())
+        .map(|i| quote::format_ident!("__field{}", i))
+        .collect()
+}
+
+fn get_sibling_value<'a>(
+    attrs: &attr::Attributes,
+    fields: &syn::Fields,
+    values: &'a [proc_macro2::TokenStream],
+    member: &syn::Member,
+    lit: &syn::LitStr,
+) -> syn::Result<&'a proc_macro2::TokenStream> {
+    for (i, (item, value)) in fields.iter().zip(values).enumerate() {
+        let item_member = match &item.ident {
+            Some(name) => syn::Member::Named(name.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(i)),
+        };
+        if item_member == *member {
+            if attrs.field(item).redact.is_some() {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    format!(
+                        "field `{}` is redacted and cannot be referenced",
+                        member.to_token_stream()
+                    ),
+                ));
+            }
+            return Ok(value);
+        }
+    }
+    Err(syn::Error::new_spanned(
+        lit,
+        format!("no field `{}` to reference", member.to_token_stream()),
+    ))
+}
+
+fn debug_value(
+    attrs: &attr::Attributes,
+    fields: &syn::Fields,
+    values: &[proc_macro2::TokenStream],
+    arrtibures: &attr::FieldAttributes,
+    value: &proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let alt = match (&arrtibures.alt, &arrtibures.alt_with) {
+        (Some(format), _) => Some(debug_format_value(attrs, fields, values, format, value)?),
+        (None, Some(with)) => Some(quote::quote!(&__DebugWith(#value, #with))),
+        (None, None) => None,
+    };
+    let normal = debug_normal_value(attrs, fields, values, arrtibures, value)?;
+    Ok(match alt {
+        Some(alt) => quote::quote!(&::derive_debug::__private::Alt(#normal, #alt)),
+        None => normal,
+    })
+}
+
+fn debug_format_value(
+    attrs: &attr::Attributes,
+    fields: &syn::Fields,
+    values: &[proc_macro2::TokenStream],
+    format: &format::FieldFormat,
+    value: &proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let lit = &format.lit;
+    let siblings = format
+        .siblings
+        .iter()
+        .map(|member| get_sibling_value(attrs, fields, values, member, lit))
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(quote::quote!(
+        &::core::format_args!(#lit, #value #(, #siblings)*)
+    ))
+}
+
+fn debug_normal_value(
+    attrs: &attr::Attributes,
+    fields: &syn::Fields,
+    values: &[proc_macro2::TokenStream],
+    arrtibures: &attr::FieldAttributes,
+    value: &proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if let Some(placeholder) = &arrtibures.redact {
+        return Ok(quote::quote!(&::core::format_args!("{}", #placeholder)));
+    }
+    if let Some(with) = &arrtibures.with {
+        return Ok(quote::quote!(&__DebugWith(#value, #with)));
+    }
+    if let Some(format) = &arrtibures.format {
+        return debug_format_value(attrs, fields, values, format, value);
+    }
+    if arrtibures.len_only {
+        return Ok(quote::quote!(
+            &::core::format_args!("<len: {}>", (#value).len())
+        ));
+    }
+    if arrtibures.hex {
+        let limit = match arrtibures.limit {
+            Some(limit) => quote::quote!(::core::option::Option::Some(#limit)),
+            None => quote::quote!(::core::option::Option::None),
+        };
+        return Ok(quote::quote!(
+            &::derive_debug::__private::Hex(
+                ::core::convert::AsRef::<[u8]>::as_ref(#value),
+                #limit,
+            )
+        ));
+    }
+    if let Some(limit) = arrtibures.limit {
+        return Ok(quote::quote!(&::derive_debug::__private::Limit(#value, #limit)));
+    }
+    // The field's own impl, through a reference so that unsized fields work
+    // too; it sees the caller's flags, such as `{:#?}` or a width.
+    Ok(quote::quote!(&#value))
+}
+
+struct FieldStatements {
+    statements: Vec<proc_macro2::TokenStream>,
+    non_exhaustive: bool,
+    // Whether `__non_exhaustive` is only known at runtime.
+    dynamic: bool,
+}
+
+#[derive(Clone, Copy)]
+enum Output {
+    // A `&mut DebugStruct` or `&mut DebugTuple` named `__debug`.
+    Builder { mark_skipped: bool },
+    // The `__visit` callback of `DebugFields::debug_fields`.
+    Visit,
+}
+
+// Statements adding each field to `output`. For a builder, flattened fields,
+// and with `mark_skipped` fields omitted by `skip_if`, also update
+// `__non_exhaustive`.
+fn debug_field_statements(
+    attrs: &attr::Attributes,
+    fields: &syn::Fields,
+    values: &[proc_macro2::TokenStream],
+    rename_all: Option<attr::RenameRule>,
+    output: Output,
+) -> syn::Result<FieldStatements> {
+    let mut res = FieldStatements {
+        statements: vec![],
+        non_exhaustive: false,
+        dynamic: false,
+    };
+    for (i, (item, value)) in fields.iter().zip(values).enumerate() {
+        let arrtibures = attrs.field(item);
+        if arrtibures.skip {
+            res.non_exhaustive = true;
+            continue;
+        }
+        let skip_if = arrtibures.skip_if.clone();
+        let name_str = get_field_name(item, arrtibures, rename_all);
+        let statement = match output {
+            Output::Builder { .. } if arrtibures.flatten => {
+                res.dynamic = true;
+                quote::quote!(
+                    __non_exhaustive |= ::derive_debug::DebugFlatten::flatten_fields(#value, __debug);
+                )
+            }
+            Output::Visit if arrtibures.flatten => {
+                quote::quote!(::derive_debug::DebugFields::debug_fields(#value, __visit);)
+            }
+            _ => {
+                let field_value = debug_value(attrs, fields, values, arrtibures, value)?;
+                match (name_str, output) {
+                    (Some(name_str), Output::Builder { .. }) => {
+                        quote::quote!(__debug.field(#name_str, #field_value);)
+                    }
+                    (None, Output::Builder { .. }) => quote::quote!(__debug.field(#field_value);),
+                    (Some(name_str), Output::Visit) => {
+                        quote::quote!(__visit(#name_str, #field_value);)
+                    }
+                    (None, Output::Visit) => {
+                        let name_str = i.to_string();
+                        quote::quote!(__visit(#name_str, #field_value);)
+                    }
+                }
+            }
+        };
+        res.statements.push(match skip_if {
+            Some(skip_if) if matches!(output, Output::Builder { mark_skipped: true }) => {
+                res.dynamic = true;
+                quote::quote!(
+                    if #skip_if(#value) {
+                        __non_exhaustive = true;
+                    } else {
+                        #statement
+                    }
+                )
+            }
+            Some(skip_if) => quote::quote!(
+                if !#skip_if(#value) {
+                    #statement
+                }
+            ),
+            None => statement,
+        });
+    }
+    Ok(res)
+}
+
+fn debug_fields(
+    attrs: &attr::Attributes,
+    name: &proc_macro2::TokenStream,
+    fields: &syn::Fields,
+    values: &[proc_macro2::TokenStream],
+    rename_all: Option<attr::RenameRule>,
+    mark_skipped: bool,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let builder = match fields {
+        syn::Fields::Named(_) => quote::quote!(f.debug_struct(#name)),
+        syn::Fields::Unnamed(_) => quote::quote!(f.debug_tuple(#name)),
+        syn::Fields::Unit => return Ok(quote::quote!(f.write_str(#name))),
+    };
+    let FieldStatements {
+        statements,
+        non_exhaustive,
+        dynamic,
+    } = debug_field_statements(
+        attrs,
+        fields,
+        values,
+        rename_all,
+        Output::Builder { mark_skipped },
+    )?;
+    let finish = if dynamic {
+        quote::quote!(if __non_exhaustive {
+            __debug.finish_non_exhaustive()
+        } else {
+            __debug.finish()
+        })
+    } else if non_exhaustive {
+        quote::quote!(__debug.finish_non_exhaustive())
+    } else {
+        quote::quote!(__debug.finish())
+    };
+    let init = if dynamic {
+        quote::quote!(let mut __non_exhaustive = #non_exhaustive;)
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+    Ok(quote::quote!({
+        let __debug = &mut #builder;
+        #init
+        #(#statements)*
+        #finish
+    }))
+}
+
+fn debug_variant(
+    attrs: &attr::Attributes,
+    variant: &syn::Variant,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let variant_name = &variant.ident;
+    let container = &attrs.container;
+    let arrtibures = attrs.variant(variant);
+    let variant_name_str = match (&arrtibures.rename, container.rename_all) {
+        (Some(rename), _) => rename.clone(),
+        (None, Some(rule)) => rule.apply_to_variant(&variant_name.unraw().to_string()),
+        (None, None) => variant_name.unraw().to_string(),
+    };
+    let bindings = get_field_bindings(&variant.fields);
+    let values: Vec<_> = bindings.iter().map(|item| quote::quote!(#item)).collect();
+    let body = debug_fields(
+        attrs,
+        &quote::quote!(#variant_name_str),
+        &variant.fields,
+        &values,
+        arrtibures.rename_all,
+        container.mark_skipped,
+    )?;
+    Ok(match &variant.fields {
+        syn::Fields::Named(syn::FieldsNamed { named, .. }) => {
+            let names = named.iter().map(|item| &item.ident);
+            quote::quote!(Self::#variant_name { #(#names: #bindings),* } => #body,)
+        }
+        syn::Fields::Unnamed(_) => quote::quote!(Self::#variant_name(#(#bindings),*) => #body,),
+        syn::Fields::Unit => quote::quote!(Self::#variant_name => #body,),
+    })
+}
+
+fn debug_with_wrapper(st: &syn::DeriveInput, attrs: &attr::Attributes) -> proc_macro2::TokenStream {
+    for item in get_all_fields(st) {
+        let arrtibures = attrs.field(item);
+        if arrtibures.is_read() && (arrtibures.with.is_some() || arrtibures.alt_with.is_some()) {
+            return quote::quote!(
+                struct __DebugWith<'__a, __T: ?Sized>(
+                    &'__a __T,
+                    fn(&__T, &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result,
+                );
+
+                impl<'__a, __T: ?Sized> ::core::fmt::Debug for __DebugWith<'__a, __T> {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        (self.1)(self.0, f)
+                    }
+                }
+            );
+        }
+    }
+    proc_macro2::TokenStream::new()
+}
+
+fn get_self_values(fields: &syn::Fields) -> Vec<proc_macro2::TokenStream> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, item)| match &item.ident {
+            Some(name) => quote::quote!(&self.#name),
+            None => {
+                let index = syn::Index::from(i);
+                quote::quote!(&self.#index)
+            }
+        })
+        .collect()
+}
+
+fn debug_body(
+    st: &syn::DeriveInput,
+    attrs: &attr::Attributes,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let container = &attrs.container;
+    let struct_name_str = match &container.name {
+        Some(name) => name.clone(),
+        None => get_struct_name(st)?.unraw().to_string(),
+    };
+    let name = match &container.alt_name {
+        Some(alt_name) => quote::quote!(if f.alternate() { #alt_name } else { #struct_name_str }),
+        None => quote::quote!(#struct_name_str),
+    };
+    match &st.data {
+        syn::Data::Struct(syn::DataStruct { fields, .. }) => {
+            let values = get_self_values(fields);
+            if container.transparent {
+                let field = fields.iter().next().unwrap();
+                let value = debug_value(attrs, fields, &values, attrs.field(field), &values[0])?;
+                return Ok(quote::quote!(::core::fmt::Debug::fmt(#value, f)));
+            }
+            debug_fields(
+                attrs,
+                &name,
+                fields,
+                &values,
+                container.rename_all,
+                container.mark_skipped,
+            )
+        }
+        syn::Data::Enum(syn::DataEnum { variants, .. }) => {
+            if variants.is_empty() {
+                return Ok(quote::quote!(match *self {}));
+            }
+            let arms = variants
+                .iter()
+                .map(|variant| debug_variant(attrs, variant))
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote::quote!(
+                match self {
+                    #(#arms)*
+                }
+            ))
+        }
+        syn::Data::Union(_) => match &container.format {
+            Some(format) => Ok(quote::quote!(f.write_fmt(::core::format_args!(#format)))),
+            None => Ok(quote::quote!(
+                f.debug_struct(#name).finish_non_exhaustive()
+            )),
+        },
+    }
+}
+
+// Only type parameters can need a bound: lifetimes and const parameters are
+// never printed themselves, whatever the fields they appear in.
+fn get_generic_names(st: &syn::DeriveInput) -> Vec<String> {
+    st.generics
+        .type_params()
+        .map(|item| item.ident.to_string())
+        .collect()
+}
+
+fn visit_field_types(
+    st: &syn::DeriveInput,
+    fields: &[&syn::Field],
+    phantoms: &[String],
+) -> Visitor {
+    let generics = get_generic_names(st);
+    let mut visitor = Visitor::new(generics, phantoms.to_vec());
+    for item in fields {
+        visitor.visit_type(&item.ty);
+    }
+    visitor
+}
+
+fn get_associat_type(visitor: &Visitor, bound: &syn::Path) -> Vec<syn::WherePredicate> {
+    visitor
+        .result
+        .iter()
+        .map(|item| syn::parse_quote!(#item: #bound))
+        .collect()
+}
+
+fn trait_bound_for_generics(generics: &mut syn::Generics, visitor: &Visitor, bound: &syn::Path) {
+    for item in generics.params.iter_mut() {
+        if let syn::GenericParam::Type(ty) = item {
+            if visitor.bounded.contains(&ty.ident.to_string()) {
+                ty.bounds.push(syn::parse_quote!(#bound));
+            }
+        }
+    }
+}
+
+// Bounds the generic parameters and projections mentioned by each field type
+// with the trait the field is used through, one trait at a time.
+fn bound_fields(
+    generics: &mut syn::Generics,
+    st: &syn::DeriveInput,
+    uses: Vec<(&syn::Field, syn::Path)>,
+    phantoms: &[String],
+) {
+    let mut bounds: Vec<(syn::Path, Vec<&syn::Field>)> = vec![];
+    for (field, bound) in uses {
+        let bound_str = bound.to_token_stream().to_string();
+        match bounds
+            .iter_mut()
+            .find(|(item, _)| item.to_token_stream().to_string() == bound_str)
+        {
+            Some((_, fields)) => fields.push(field),
+            None => bounds.push((bound, vec![field])),
+        }
+    }
+    for (bound, fields) in &bounds {
+        let visitor = visit_field_types(st, fields, phantoms);
+        trait_bound_for_generics(generics, &visitor, bound);
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(get_associat_type(&visitor, bound));
+    }
+}
+
+fn get_field_bounds(st: &syn::DeriveInput, attrs: &attr::Attributes) -> Vec<syn::WherePredicate> {
+    let generics = get_generic_names(st);
+    let mut res = vec![];
+    for item in get_all_fields(st) {
+        let arrtibures = attrs.field(item);
+        if !arrtibures.is_read() {
+            continue;
+        }
+        if arrtibures.bound.is_none() && arrtibures.flatten {
+            let mut mentions = Mentions {
+                generics: &generics,
+                found: false,
+            };
+            mentions.visit_type(&item.ty);
+            if mentions.found {
+                let ty = &item.ty;
+                res.push(syn::parse_quote!(#ty: ::derive_debug::DebugFlatten));
+            }
+        }
+        res.extend(arrtibures.bound.iter().flatten().cloned());
+    }
+    res
+}
+
+fn impl_debug_flatten(
+    st: &syn::DeriveInput,
+    attrs: &attr::Attributes,
+    generics: &syn::Generics,
+    wrapper: &proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let container = &attrs.container;
+    let fields = match &st.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: fields @ syn::Fields::Named(_),
+            ..
+        }) if !container.transparent => fields,
+        _ => return Ok(proc_macro2::TokenStream::new()),
+    };
+    let struct_name = get_struct_name(st)?;
+    let values = get_self_values(fields);
+    let FieldStatements {
+        statements,
+        non_exhaustive,
+        dynamic,
+    } = debug_field_statements(
+        attrs,
+        fields,
+        &values,
+        container.rename_all,
+        Output::Builder {
+            mark_skipped: container.mark_skipped,
+        },
+    )?;
+    let init = if dynamic {
+        quote::quote!(let mut __non_exhaustive = #non_exhaustive;)
+    } else {
+        quote::quote!(let __non_exhaustive = #non_exhaustive;)
+    };
+    let (a, b, c) = generics.split_for_impl();
+    Ok(quote::quote!(
+        impl #a ::derive_debug::DebugFlatten for #struct_name #b #c {
+            fn flatten_fields(&self, __debug: &mut ::core::fmt::DebugStruct<'_, '_>) -> bool {
+                #wrapper
+                #init
+                #(#statements)*
+                __non_exhaustive
+            }
+        }
+    ))
+}
+
+fn debug_fields_body(
+    attrs: &attr::Attributes,
+    fields: &syn::Fields,
+    values: &[proc_macro2::TokenStream],
+    rename_all: Option<attr::RenameRule>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let statements =
+        debug_field_statements(attrs, fields, values, rename_all, Output::Visit)?.statements;
+    Ok(quote::quote!({ #(#statements)* }))
+}
+
+fn impl_debug_fields(
+    st: &syn::DeriveInput,
+    attrs: &attr::Attributes,
+    generics: &syn::Generics,
+    wrapper: &proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let container = &attrs.container;
+    // Like DebugFlatten, the impl comes with every struct that has named
+    // fields, so that it can be flattened into a `fields` container.
+    let named = matches!(
+        &st.data,
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(_),
+            ..
+        })
+    );
+    if !container.fields && (!named || container.transparent) {
+        return Ok(proc_macro2::TokenStream::new());
+    }
+    let body = match &st.data {
+        syn::Data::Struct(syn::DataStruct { fields, .. }) => debug_fields_body(
+            attrs,
+            fields,
+            &get_self_values(fields),
+            container.rename_all,
+        )?,
+        syn::Data::Enum(syn::DataEnum { variants, .. }) if variants.is_empty() => {
+            quote::quote!(match *self {})
+        }
+        syn::Data::Enum(syn::DataEnum { variants, .. }) => {
+            let mut arms = vec![];
+            for variant in variants {
+                let variant_name = &variant.ident;
+                let bindings = get_field_bindings(&variant.fields);
+                let values: Vec<_> = bindings.iter().map(|item| quote::quote!(#item)).collect();
+                let rename_all = attrs.variant(variant).rename_all;
+                let body = debug_fields_body(attrs, &variant.fields, &values, rename_all)?;
+                arms.push(match &variant.fields {
+                    syn::Fields::Named(syn::FieldsNamed { named, .. }) => {
+                        let names = named.iter().map(|item| &item.ident);
+                        quote::quote!(Self::#variant_name { #(#names: #bindings),* } => #body)
+                    }
+                    syn::Fields::Unnamed(_) => {
+                        quote::quote!(Self::#variant_name(#(#bindings),*) => #body)
+                    }
+                    syn::Fields::Unit => quote::quote!(Self::#variant_name => {}),
+                });
+            }
+            quote::quote!(
+                match self {
+                    #(#arms)*
+                }
+            )
+        }
+        // Union fields are never read.
+        syn::Data::Union(_) => proc_macro2::TokenStream::new(),
+    };
+
+    // Flattened fields are visited through their own DebugFields impl.
+    let mut generics = generics.clone();
+    if container.bound.is_none() {
+        let names = get_generic_names(st);
+        for item in get_all_fields(st) {
+            let arrtibures = attrs.field(item);
+            let mut mentions = Mentions {
+                generics: &names,
+                found: false,
+            };
+            mentions.visit_type(&item.ty);
+            if arrtibures.is_read()
+                && arrtibures.flatten
+                && arrtibures.bound.is_none()
+                && mentions.found
+            {
+                let ty = &item.ty;
+                generics
+                    .make_where_clause()
+                    .predicates
+                    .push(syn::parse_quote!(#ty: ::derive_debug::DebugFields));
+            }
+        }
+    }
+
+    let struct_name = get_struct_name(st)?;
+    let (a, b, c) = generics.split_for_impl();
+    Ok(quote::quote!(
+        impl #a ::derive_debug::DebugFields for #struct_name #b #c {
+            fn debug_fields(
+                &self,
+                __visit: &mut dyn ::core::ops::FnMut(&'static str, &dyn ::core::fmt::Debug),
+            ) {
+                #wrapper
+                #body
+            }
+        }
+    ))
+}
+
+fn impl_debug_for_struct(
+    st: &syn::DeriveInput,
+    attrs: &attr::Attributes,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let struct_name = get_struct_name(st)?;
+    let wrapper = debug_with_wrapper(st, attrs);
+    let body = debug_body(st, attrs)?;
+
+    let container = &attrs.container;
+    let mut generics = st.generics.clone();
+    match &container.bound {
+        Some(bounds) => generics
+            .make_where_clause()
+            .predicates
+            .extend(bounds.clone()),
+        None => {
+            bound_fields(&mut generics, st, get_field_uses(st, attrs), &container.phantom);
+            generics
+                .make_where_clause()
+                .predicates
+                .extend(get_field_bounds(st, attrs));
+        }
+    }
+    let flatten = impl_debug_flatten(st, attrs, &generics, &wrapper)?;
+    let debug_fields = impl_debug_fields(st, attrs, &generics, &wrapper)?;
+    let (a, b, c) = generics.split_for_impl();
+    let token = quote::quote!(
+        impl #a ::core::fmt::Debug for #struct_name #b #c{
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result{
+                #wrapper
+                #body
+            }
+        }
+
+        #flatten
+
+        #debug_fields
+    );
+    Ok(token)
+}
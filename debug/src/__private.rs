@@ -0,0 +1,97 @@
+// Support code for the renderers generated by `#[derive(CustomDebug)]`. Not
+// public API.
+
+use core::fmt::{self, Debug};
+
+// `#[debug(limit = N)]`: at most N elements of anything iterable by reference.
+pub struct Limit<'a, T: ?Sized>(pub &'a T, pub usize);
+
+impl<'a, T> Debug for Limit<'a, T>
+where
+    T: ?Sized,
+    &'a T: IntoIterator,
+    <&'a T as IntoIterator>::Item: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        let mut iter = self.0.into_iter();
+        list.entries(iter.by_ref().take(self.1));
+        let rest = iter.count();
+        if rest > 0 {
+            list.entry(&format_args!("… and {} more", rest));
+        }
+        list.finish()
+    }
+}
+
+// `#[debug(hex)]`: bytes as `[de ad be ef]`, or as a classic hex dump with
+// offsets and an ASCII column under `{:#?}`. An optional limit truncates it.
+pub struct Hex<'a>(pub &'a [u8], pub Option<usize>);
+
+impl Debug for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (shown, rest) = match self.1 {
+            Some(limit) if limit < self.0.len() => (&self.0[..limit], self.0.len() - limit),
+            _ => (self.0, 0),
+        };
+        if !f.alternate() {
+            f.write_str("[")?;
+            for (i, byte) in shown.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(" ")?;
+                }
+                write!(f, "{:02x}", byte)?;
+            }
+            if rest > 0 {
+                if !shown.is_empty() {
+                    f.write_str(" ")?;
+                }
+                write!(f, "… and {} more bytes", rest)?;
+            }
+            return f.write_str("]");
+        }
+        if shown.is_empty() && rest == 0 {
+            return f.write_str("[]");
+        }
+        f.write_str("[\n")?;
+        for (i, line) in shown.chunks(16).enumerate() {
+            write!(f, "    {:08x} ", i * 16)?;
+            for j in 0..16 {
+                if j % 8 == 0 {
+                    f.write_str(" ")?;
+                }
+                match line.get(j) {
+                    Some(byte) => write!(f, "{:02x} ", byte)?,
+                    None => f.write_str("   ")?,
+                }
+            }
+            f.write_str(" |")?;
+            for byte in line {
+                if byte.is_ascii_graphic() || *byte == b' ' {
+                    write!(f, "{}", *byte as char)?;
+                } else {
+                    f.write_str(".")?;
+                }
+            }
+            f.write_str("|\n")?;
+        }
+        if rest > 0 {
+            writeln!(f, "    … and {} more bytes", rest)?;
+        }
+        f.write_str("]")
+    }
+}
+
+// `#[debug(alt = "...")]` and `#[debug(alt_with = "...")]`: the second
+// rendering replaces the first under `{:#?}`.
+pub struct Alt<'a>(pub &'a dyn Debug, pub &'a dyn Debug);
+
+impl Debug for Alt<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            self.1.fmt(f)
+        } else {
+            self.0.fmt(f)
+        }
+    }
+}
@@ -0,0 +1,49 @@
+// Enums are formatted variant by variant: unit variants print their bare name,
+// tuple variants go through debug_tuple and struct variants through
+// debug_struct. Per-field #[debug = "..."] formats apply inside variants too,
+// and the inferred bounds take the fields of every variant into account.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[derive(CustomDebug)]
+pub enum State<T, U> {
+    Idle,
+    Running(T, #[debug = "0x{:02x}"] u8),
+    Stopped {
+        #[debug = "{}s"]
+        after: u32,
+        reason: U,
+    },
+    Marker(PhantomData<U>),
+}
+
+#[derive(CustomDebug)]
+pub enum Never {}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    struct NotDebug;
+
+    assert_debug::<State<u8, &str>>();
+    assert_debug::<Never>();
+
+    let idle: State<u8, &str> = State::Idle;
+    assert_eq!(format!("{:?}", idle), "Idle");
+
+    let running: State<&str, &str> = State::Running("job", 0x1f);
+    assert_eq!(format!("{:?}", running), r#"Running("job", 0x1f)"#);
+
+    let stopped: State<u8, &str> = State::Stopped {
+        after: 3,
+        reason: "done",
+    };
+    assert_eq!(
+        format!("{:?}", stopped),
+        r#"Stopped { after: 3s, reason: "done" }"#,
+    );
+
+    let _ = State::<u8, NotDebug>::Marker(PhantomData);
+}
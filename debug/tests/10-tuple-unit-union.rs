@@ -0,0 +1,44 @@
+// Tuple structs print through debug_tuple and accept #[debug = "..."] on their
+// positional fields, unit structs print as their bare name, and unions either
+// print an opaque `Name { .. }` or the format given on the union itself, since
+// their fields cannot be read safely.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+#[derive(CustomDebug)]
+pub struct Meters(#[debug = "{}m"] u32);
+
+#[derive(CustomDebug)]
+pub struct Pair<T>(T, #[debug = "0b{:04b}"] u8);
+
+#[derive(CustomDebug)]
+pub struct Unit;
+
+#[derive(CustomDebug)]
+pub union Bits<T: Copy> {
+    int: u32,
+    other: T,
+}
+
+#[derive(CustomDebug)]
+#[debug = "Float(<opaque>)"]
+pub union Float {
+    int: u32,
+    float: f32,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    #[derive(Clone, Copy)]
+    struct NotDebug;
+
+    assert_debug::<Bits<NotDebug>>();
+
+    assert_eq!(format!("{:?}", Meters(5)), "Meters(5m)");
+    assert_eq!(format!("{:?}", Pair("a", 5)), r#"Pair("a", 0b0101)"#);
+    assert_eq!(format!("{:?}", Unit), "Unit");
+    assert_eq!(format!("{:?}", Bits::<u8> { int: 1 }), "Bits { .. }");
+    assert_eq!(format!("{:?}", Float { float: 1.0 }), "Float(<opaque>)");
+}
@@ -0,0 +1,64 @@
+// #[debug(skip)] leaves a field out of the output entirely and marks the
+// struct as non-exhaustive, while #[debug(redact)] keeps the field name but
+// prints a placeholder in place of the value. Neither kind of field is ever
+// formatted, so neither contributes a Debug bound.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+#[derive(CustomDebug)]
+pub struct Credentials<K, S> {
+    user: &'static str,
+    #[debug(redact)]
+    password: S,
+    #[debug(redact = "***")]
+    token: String,
+    #[debug(skip)]
+    key: K,
+}
+
+#[derive(CustomDebug)]
+pub struct Handle(u32, #[debug(skip)] String);
+
+#[derive(CustomDebug)]
+pub enum Auth<S> {
+    Anonymous,
+    Basic {
+        user: &'static str,
+        #[debug(redact)]
+        password: S,
+    },
+    Token(#[debug(skip)] S),
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    struct NotDebug;
+
+    assert_debug::<Credentials<NotDebug, NotDebug>>();
+    assert_debug::<Auth<NotDebug>>();
+
+    let credentials = Credentials {
+        user: "admin",
+        password: "hunter2",
+        token: "secret".to_owned(),
+        key: 0u64,
+    };
+    assert_eq!(
+        format!("{:?}", credentials),
+        r#"Credentials { user: "admin", password: [REDACTED], token: ***, .. }"#,
+    );
+
+    assert_eq!(format!("{:?}", Handle(7, "fd".to_owned())), "Handle(7, ..)");
+
+    let basic = Auth::Basic {
+        user: "admin",
+        password: "hunter2",
+    };
+    assert_eq!(
+        format!("{:?}", basic),
+        r#"Basic { user: "admin", password: [REDACTED] }"#,
+    );
+    assert_eq!(format!("{:?}", Auth::Token("secret")), "Token(..)");
+}
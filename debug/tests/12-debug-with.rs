@@ -0,0 +1,61 @@
+// #[debug(with = "path")] renders a field through a function with the
+// signature `fn(&T, &mut fmt::Formatter) -> fmt::Result` instead of through
+// Debug. This covers foreign types that have no Debug impl as well as compact
+// renderings of large values. The field type does not need to implement Debug.
+
+use derive_debug::CustomDebug;
+use std::fmt::{self, Debug};
+
+pub struct Foreign(u32);
+
+mod render {
+    use std::fmt;
+
+    pub fn foreign(value: &super::Foreign, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Foreign#{}", value.0)
+    }
+
+    pub fn byte_count<T: AsRef<[u8]>>(value: &T, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{} bytes>", value.as_ref().len())
+    }
+}
+
+fn opaque<T>(_: &T, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("<opaque>")
+}
+
+#[derive(CustomDebug)]
+pub struct Packet<T> {
+    #[debug(with = "render::foreign")]
+    source: Foreign,
+    #[debug(with = "render::byte_count")]
+    payload: Vec<u8>,
+    #[debug(with = "opaque")]
+    extra: T,
+}
+
+#[derive(CustomDebug)]
+pub enum Message<T> {
+    Data(#[debug(with = "opaque")] T),
+    Empty,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    struct NotDebug;
+
+    assert_debug::<Packet<NotDebug>>();
+    assert_debug::<Message<NotDebug>>();
+
+    let packet = Packet {
+        source: Foreign(7),
+        payload: vec![0; 4096],
+        extra: NotDebug,
+    };
+    assert_eq!(
+        format!("{:?}", packet),
+        "Packet { source: Foreign#7, payload: <4096 bytes>, extra: <opaque> }",
+    );
+    assert_eq!(format!("{:?}", Message::Data(NotDebug)), "Data(<opaque>)");
+}
@@ -0,0 +1,64 @@
+// The escape hatch adds its predicates to the where-clause the type was
+// declared with rather than replacing it, so `where T: Trait` keeps compiling.
+// Trailing commas in the bound are fine.
+//
+// A #[debug(bound = "...")] on a single field replaces inference for that
+// field only; the other fields still get their usual inferred bounds.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+pub trait Trait {
+    type Value;
+}
+
+#[derive(CustomDebug)]
+#[debug(bound = "T::Value: Debug,")]
+pub struct Wrapper<T>
+where
+    T: Trait,
+{
+    values: Vec<T::Value>,
+}
+
+#[derive(CustomDebug)]
+pub struct Mixed<T, U>
+where
+    T: Trait,
+{
+    #[debug(bound = "T::Value: Debug")]
+    values: Vec<T::Value>,
+    plain: U,
+}
+
+#[derive(CustomDebug)]
+pub struct Unbounded<T> {
+    #[debug(bound = "")]
+    #[debug(with = "len")]
+    items: Vec<T>,
+}
+
+fn len<T>(items: &Vec<T>, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "{} items", items.len())
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    struct Id;
+    struct NotDebug;
+
+    impl Trait for Id {
+        type Value = u8;
+    }
+
+    assert_debug::<Wrapper<Id>>();
+    assert_debug::<Mixed<Id, u8>>();
+    assert_debug::<Unbounded<NotDebug>>();
+
+    let mixed = Mixed::<Id, &str> {
+        values: vec![1, 2],
+        plain: "x",
+    };
+    assert_eq!(format!("{:?}", mixed), r#"Mixed { values: [1, 2], plain: "x" }"#);
+}
@@ -0,0 +1,17 @@
+// A malformed bound is reported on the attribute that contains it.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(bound = "T: Debug, where")]
+pub struct Wrapper<T> {
+    value: T,
+}
+
+#[derive(CustomDebug)]
+pub struct Field<T> {
+    #[debug(bound = "T Debug")]
+    value: T,
+}
+
+fn main() {}
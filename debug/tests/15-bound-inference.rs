@@ -0,0 +1,105 @@
+// Bound inference looks through every shape of field type. A type parameter
+// that is reached through references, slices, arrays, tuples or ordinary
+// generic types must be Debug. Qualified paths and trait objects are bounded
+// as a whole. Raw pointers and fn pointers are always Debug, so they add no
+// bound at all.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+pub trait Trait {
+    type Value;
+}
+
+pub trait Show<T>: Debug {}
+
+#[derive(CustomDebug)]
+pub struct Qualified<T: Trait> {
+    value: <T as Trait>::Value,
+    values: Vec<<T as Trait>::Value>,
+}
+
+#[derive(CustomDebug)]
+pub struct Reference<'a, T> {
+    value: &'a T,
+}
+
+#[derive(CustomDebug)]
+pub struct Array<T> {
+    values: [T; 2],
+}
+
+#[derive(CustomDebug)]
+pub struct Tuple<T, U> {
+    pair: (T, U),
+}
+
+#[derive(CustomDebug)]
+pub struct Pointers<T, U> {
+    callback: fn(T) -> U,
+    raw: *const T,
+}
+
+#[derive(CustomDebug)]
+pub struct Object<T> {
+    object: Box<dyn Show<T>>,
+}
+
+#[derive(CustomDebug)]
+pub struct BoxedSlice<T> {
+    values: Box<[T]>,
+}
+
+#[derive(CustomDebug)]
+pub struct Projection<'a, T: Trait> {
+    value: Option<&'a T::Value>,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    struct NotDebug;
+
+    impl Trait for NotDebug {
+        type Value = u8;
+    }
+
+    #[derive(Debug)]
+    struct Shown;
+
+    impl Show<NotDebug> for Shown {}
+
+    assert_debug::<Qualified<NotDebug>>();
+    assert_debug::<Reference<u8>>();
+    assert_debug::<Array<u8>>();
+    assert_debug::<Tuple<u8, &str>>();
+    assert_debug::<Pointers<NotDebug, NotDebug>>();
+    assert_debug::<Object<NotDebug>>();
+    assert_debug::<BoxedSlice<u8>>();
+    assert_debug::<Projection<NotDebug>>();
+
+    let qualified = Qualified::<NotDebug> {
+        value: 1,
+        values: vec![2, 3],
+    };
+    assert_eq!(
+        format!("{:?}", qualified),
+        "Qualified { value: 1, values: [2, 3] }",
+    );
+
+    let reference = Reference { value: &"a" };
+    assert_eq!(format!("{:?}", reference), r#"Reference { value: "a" }"#);
+
+    let tuple = Tuple { pair: (1, "b") };
+    assert_eq!(format!("{:?}", tuple), r#"Tuple { pair: (1, "b") }"#);
+
+    let object = Object::<NotDebug> {
+        object: Box::new(Shown),
+    };
+    assert_eq!(format!("{:?}", object), "Object { object: Shown }");
+
+    let boxed = BoxedSlice {
+        values: vec![1, 2].into_boxed_slice(),
+    };
+    assert_eq!(format!("{:?}", boxed), "BoxedSlice { values: [1, 2] }");
+}
@@ -0,0 +1,64 @@
+// Types listed in #[debug(phantom = "...")] are treated like PhantomData: the
+// type parameters they wrap do not need to be Debug. Entries may be written as
+// paths, in which case only the last segment is compared.
+//
+// PhantomData itself is always recognized, whatever it wraps, so that
+// PhantomData<fn() -> T> and PhantomData<*const T> add no bound either.
+
+use derive_debug::CustomDebug;
+use std::fmt::{self, Debug};
+use std::marker::{PhantomData, PhantomPinned};
+
+pub struct TypeTag<T>(PhantomData<T>);
+
+impl<T> Debug for TypeTag<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("TypeTag")
+    }
+}
+
+pub mod marker {
+    use std::fmt::{self, Debug};
+    use std::marker::PhantomData;
+
+    pub struct Invariant<T>(pub PhantomData<fn(T) -> T>);
+
+    impl<T> Debug for Invariant<T> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("Invariant")
+        }
+    }
+}
+
+#[derive(CustomDebug)]
+#[debug(phantom = "TypeTag, marker::Invariant")]
+pub struct Tagged<T, U> {
+    tag: TypeTag<T>,
+    invariant: marker::Invariant<U>,
+    pinned: PhantomPinned,
+}
+
+#[derive(CustomDebug)]
+pub struct Markers<T, U> {
+    output: PhantomData<fn() -> T>,
+    pointer: PhantomData<*const U>,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    struct NotDebug;
+
+    assert_debug::<Tagged<NotDebug, NotDebug>>();
+    assert_debug::<Markers<NotDebug, NotDebug>>();
+
+    let tagged = Tagged::<NotDebug, NotDebug> {
+        tag: TypeTag(PhantomData),
+        invariant: marker::Invariant(PhantomData),
+        pinned: PhantomPinned,
+    };
+    assert_eq!(
+        format!("{:?}", tagged),
+        "Tagged { tag: TypeTag, invariant: Invariant, pinned: PhantomPinned }",
+    );
+}
@@ -0,0 +1,76 @@
+// Besides the `{}` placeholder for the field itself, a #[debug = "..."] format
+// may refer to other fields of the same struct or variant as `{self.name}`,
+// with or without a format spec of their own. Generic fields, including
+// skipped siblings, are bounded by the traits their placeholders name.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Timeout {
+    #[debug = "{} {self.unit}"]
+    value: u64,
+    #[debug(skip)]
+    unit: &'static str,
+    #[debug = "{:>4}/{self.limit:03}"]
+    retries: u8,
+    limit: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct Scaled(#[debug = "{}e{self.1}"] u32, i8);
+
+#[derive(CustomDebug)]
+pub enum Reading {
+    Temperature {
+        #[debug = "{:.1}°{self.scale}"]
+        value: f32,
+        #[debug(skip)]
+        scale: char,
+    },
+}
+
+#[derive(CustomDebug)]
+pub struct Timed<T> {
+    #[debug = "{}ms"]
+    value: T,
+}
+
+#[derive(CustomDebug)]
+pub struct Register<T, U> {
+    #[debug = "{:#x} ({self.name})"]
+    bits: T,
+    #[debug(skip)]
+    name: U,
+}
+
+fn main() {
+    let timeout = Timeout {
+        value: 250,
+        unit: "ms",
+        retries: 2,
+        limit: 5,
+    };
+    assert_eq!(
+        format!("{:?}", timeout),
+        "Timeout { value: 250 ms, retries:    2/005, limit: 5, .. }",
+    );
+
+    assert_eq!(format!("{:?}", Scaled(15, -3)), "Scaled(15e-3, -3)");
+
+    let reading = Reading::Temperature {
+        value: 21.46,
+        scale: 'C',
+    };
+    assert_eq!(format!("{:?}", reading), "Temperature { value: 21.5°C, .. }");
+
+    assert_eq!(format!("{:?}", Timed { value: 40 }), "Timed { value: 40ms }");
+
+    let register = Register {
+        bits: 0x2au8,
+        name: "status",
+    };
+    assert_eq!(
+        format!("{:?}", register),
+        "Register { bits: 0x2a (status), .. }",
+    );
+}
@@ -0,0 +1,38 @@
+// Mistakes in a #[debug = "..."] format string are reported on the string
+// literal itself rather than somewhere inside the generated impl.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct TwoPlaceholders {
+    #[debug = "{} {}"]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct UnknownTrait {
+    #[debug = "{:q}"]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct Unterminated {
+    #[debug = "{:?"]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct MissingSibling {
+    #[debug = "{} {self.unit}"]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct RedactedSibling {
+    #[debug = "{} {self.secret}"]
+    value: u8,
+    #[debug(redact)]
+    secret: String,
+}
+
+fn main() {}
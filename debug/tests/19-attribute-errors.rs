@@ -0,0 +1,39 @@
+// Attributes that belong to other derives or tools are left alone, even when
+// they are not valid meta syntax. Mistakes inside #[debug(...)] are reported
+// with a span on the offending key, with a suggestion for near misses, and all
+// of them are reported at once rather than one per compilation.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(bonud = "T: Clone")]
+pub struct Container<T> {
+    #[doc = concat!("the ", "value")]
+    #[debug(skp)]
+    value: T,
+    #[debug(colour)]
+    extra: u8,
+    #[debug(skip = "yes")]
+    flag: bool,
+    #[debug(with = 1)]
+    with: u8,
+    #[debug(redact, with = "fmt")]
+    secret: String,
+    #[debug(redact, alt = "{}")]
+    password: String,
+    #[debug(alt = "{}", alt_with = "fmt")]
+    pretty: u8,
+}
+
+#[derive(CustomDebug)]
+#[debug(rename_all = "camelcase")]
+pub struct Tuple(#[debug(rename = "first")] u8);
+
+#[derive(CustomDebug)]
+#[debug(alt_name = "E")]
+pub enum Enum {
+    #[debug(skip)]
+    Variant(#[debug(skip, skip)] u8),
+}
+
+fn main() {}
@@ -0,0 +1,118 @@
+// #[debug(transparent)] on a single-field struct prints the field exactly as
+// its own Debug impl would, without the wrapper's name around it, honoring
+// flags such as {:#?} or a width and precision.
+//
+// #[debug(flatten)] inlines the fields of a nested CustomDebug struct into the
+// parent's output. It relies on the DebugFlatten trait that the derive
+// implements for every struct with named fields.
+
+use derive_debug::{CustomDebug, DebugFlatten};
+use std::fmt::Debug;
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct UserId(u64);
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Port {
+    #[debug = "{}/tcp"]
+    number: u16,
+}
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Num(f64);
+
+#[derive(CustomDebug)]
+pub struct Inner {
+    a: u8,
+    b: u8,
+}
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Wrap(Inner);
+
+#[derive(CustomDebug)]
+pub struct Network<T> {
+    host: &'static str,
+    port: Port,
+    #[debug(skip)]
+    retries: T,
+}
+
+#[derive(CustomDebug)]
+pub struct Limits {
+    connections: u32,
+}
+
+#[derive(CustomDebug)]
+pub struct Config<T> {
+    name: &'static str,
+    #[debug(flatten)]
+    network: Network<T>,
+    #[debug(flatten)]
+    limits: Limits,
+}
+
+#[derive(CustomDebug)]
+pub enum Service {
+    Local {
+        owner: UserId,
+        #[debug(flatten)]
+        limits: Limits,
+    },
+}
+
+fn assert_debug<F: Debug>() {}
+fn assert_flatten<F: DebugFlatten>() {}
+
+fn main() {
+    struct NotDebug;
+
+    assert_debug::<Config<NotDebug>>();
+    assert_flatten::<Config<NotDebug>>();
+
+    assert_eq!(format!("{:?}", UserId(7)), "7");
+    assert_eq!(format!("{:?}", Port { number: 80 }), "80/tcp");
+    assert_eq!(format!("{:>8.2?}", Num(1.23456)), format!("{:>8.2?}", 1.23456));
+    assert_eq!(
+        format!("{:#?}", Wrap(Inner { a: 1, b: 2 })),
+        "Inner {\n    a: 1,\n    b: 2,\n}",
+    );
+
+    let config = Config {
+        name: "api",
+        network: Network {
+            host: "localhost",
+            port: Port { number: 8080 },
+            retries: 3,
+        },
+        limits: Limits { connections: 64 },
+    };
+    assert_eq!(
+        format!("{:?}", config),
+        r#"Config { name: "api", host: "localhost", port: 8080/tcp, connections: 64, .. }"#,
+    );
+
+    assert_eq!(
+        format!("{:#?}", config),
+        r#"Config {
+    name: "api",
+    host: "localhost",
+    port: 8080/tcp,
+    connections: 64,
+    ..
+}"#,
+    );
+
+    let service = Service::Local {
+        owner: UserId(1),
+        limits: Limits { connections: 8 },
+    };
+    assert_eq!(
+        format!("{:?}", service),
+        "Local { owner: 1, connections: 8 }",
+    );
+}
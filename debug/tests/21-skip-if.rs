@@ -0,0 +1,67 @@
+// #[debug(skip_if = "path")] omits a field at runtime whenever the predicate,
+// called with a reference to the field, returns true. By default omitted
+// fields leave no trace in the output; with #[debug(mark_skipped)] on the type
+// the output ends in `..` whenever something was left out.
+
+use derive_debug::CustomDebug;
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+#[derive(CustomDebug)]
+pub struct Span {
+    name: &'static str,
+    #[debug(skip_if = "Option::is_none")]
+    parent: Option<u64>,
+    #[debug(skip_if = "Vec::is_empty")]
+    tags: Vec<&'static str>,
+    #[debug(skip_if = "is_zero")]
+    retries: u32,
+}
+
+#[derive(CustomDebug)]
+#[debug(mark_skipped)]
+pub struct Marked {
+    #[debug(skip_if = "Option::is_none")]
+    parent: Option<u64>,
+    id: u64,
+}
+
+#[derive(CustomDebug)]
+pub enum Event {
+    Log(#[debug(skip_if = "str::is_empty")] &'static str, u8),
+}
+
+fn main() {
+    let sparse = Span {
+        name: "request",
+        parent: None,
+        tags: vec![],
+        retries: 0,
+    };
+    assert_eq!(format!("{:?}", sparse), r#"Span { name: "request" }"#);
+
+    let full = Span {
+        name: "request",
+        parent: Some(1),
+        tags: vec!["db"],
+        retries: 2,
+    };
+    assert_eq!(
+        format!("{:?}", full),
+        r#"Span { name: "request", parent: Some(1), tags: ["db"], retries: 2 }"#,
+    );
+
+    let marked = Marked { parent: None, id: 3 };
+    assert_eq!(format!("{:?}", marked), "Marked { id: 3, .. }");
+
+    let marked = Marked {
+        parent: Some(2),
+        id: 3,
+    };
+    assert_eq!(format!("{:?}", marked), "Marked { parent: Some(2), id: 3 }");
+
+    assert_eq!(format!("{:?}", Event::Log("", 1)), "Log(1)");
+    assert_eq!(format!("{:?}", Event::Log("x", 1)), r#"Log("x", 1)"#);
+}
@@ -0,0 +1,66 @@
+// Built-in renderers for fields that are too large to print in full:
+//
+//   #[debug(limit = N)]  at most N elements of anything iterable by reference,
+//                        followed by a count of the rest
+//   #[debug(hex)]        bytes as hex, or as a hex dump under {:#?}; combines
+//                        with `limit` to truncate the dump
+//   #[debug(len_only)]   only the length of the field
+
+use derive_debug::CustomDebug;
+use std::collections::BTreeMap;
+
+#[derive(CustomDebug)]
+pub struct Frame<T> {
+    #[debug(limit = 3)]
+    samples: Vec<T>,
+    #[debug(limit = 1)]
+    headers: BTreeMap<&'static str, u8>,
+    #[debug(hex)]
+    magic: [u8; 4],
+    #[debug(hex, limit = 2)]
+    payload: Vec<u8>,
+    #[debug(len_only)]
+    buffer: Box<[u8]>,
+}
+
+#[derive(CustomDebug)]
+pub struct Packet {
+    #[debug(hex)]
+    bytes: Vec<u8>,
+}
+
+fn main() {
+    let mut headers = BTreeMap::new();
+    headers.insert("a", 1);
+    headers.insert("b", 2);
+
+    let frame = Frame {
+        samples: (0..10000).collect(),
+        headers,
+        magic: [0xde, 0xad, 0xbe, 0xef],
+        payload: vec![1, 2, 3, 4],
+        buffer: vec![0; 4 << 20].into_boxed_slice(),
+    };
+    assert_eq!(
+        format!("{:?}", frame),
+        concat!(
+            "Frame { samples: [0, 1, 2, … and 9997 more], ",
+            r#"headers: [("a", 1), … and 1 more], "#,
+            "magic: [de ad be ef], ",
+            "payload: [01 02 … and 2 more bytes], ",
+            "buffer: <len: 4194304> }",
+        ),
+    );
+
+    let packet = Packet {
+        bytes: b"Hello, hex dump!\x00\x01".to_vec(),
+    };
+    let expected = "\
+Packet {
+    bytes: [
+        00000000  48 65 6c 6c 6f 2c 20 68  65 78 20 64 75 6d 70 21  |Hello, hex dump!|
+        00000010  00 01                                             |..|
+    ],
+}";
+    assert_eq!(format!("{:#?}", packet), expected);
+}
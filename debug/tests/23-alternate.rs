@@ -0,0 +1,100 @@
+// A field can render differently under {:#?} than under {:?}, typically a
+// compact summary normally and the full value when asked for pretty output:
+//
+//   #[debug(alt = "...")]       format string used under {:#?}, with the same
+//                               syntax as #[debug = "..."]
+//   #[debug(alt_with = "path")] function used under {:#?}, like `with`
+//
+// and #[debug(alt_name = "...")] on the type replaces its name under {:#?}.
+// The alternate flag is read from the formatter passed to each field, so this
+// also works for fields that are flattened into another struct.
+
+use derive_debug::CustomDebug;
+use std::fmt;
+
+fn dump(items: &Vec<u32>, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_set().entries(items).finish()
+}
+
+#[derive(CustomDebug)]
+#[debug(alt_name = "Request (pretty)")]
+pub struct Request {
+    #[debug(alt = "{:#x} (of {self.count})")]
+    id: u32,
+    #[debug(len_only, alt_with = "dump")]
+    items: Vec<u32>,
+    count: usize,
+}
+
+#[derive(CustomDebug)]
+pub struct Envelope {
+    #[debug(alt = "<{}>")]
+    tag: &'static str,
+    #[debug(flatten)]
+    request: Request,
+}
+
+#[derive(CustomDebug)]
+#[debug(alt_name = "Pair!")]
+pub struct Pair(#[debug(alt = "{:?}u8")] u8, u8);
+
+#[derive(CustomDebug)]
+pub enum Event {
+    Ping {
+        #[debug(alt = "seq #{}")]
+        seq: u64,
+    },
+}
+
+fn main() {
+    let request = Request {
+        id: 255,
+        items: vec![1, 2],
+        count: 2,
+    };
+    assert_eq!(
+        format!("{:?}", request),
+        "Request { id: 255, items: <len: 2>, count: 2 }",
+    );
+    assert_eq!(
+        format!("{:#?}", request),
+        "\
+Request (pretty) {
+    id: 0xff (of 2),
+    items: {
+        1,
+        2,
+    },
+    count: 2,
+}",
+    );
+
+    let envelope = Envelope {
+        tag: "req",
+        request,
+    };
+    assert_eq!(
+        format!("{:?}", envelope),
+        r#"Envelope { tag: "req", id: 255, items: <len: 2>, count: 2 }"#,
+    );
+    assert_eq!(
+        format!("{:#?}", envelope),
+        "\
+Envelope {
+    tag: <req>,
+    id: 0xff (of 2),
+    items: {
+        1,
+        2,
+    },
+    count: 2,
+}",
+    );
+
+    assert_eq!(format!("{:?}", Pair(1, 2)), "Pair(1, 2)");
+    assert_eq!(format!("{:#?}", Pair(1, 2)), "Pair!(\n    1u8,\n    2,\n)");
+
+    let event = Event::Ping { seq: 7 };
+    assert_eq!(format!("{:?}", event), "Ping { seq: 7 }");
+    assert_eq!(format!("{:#?}", event), "Ping {\n    seq: seq #7,\n}");
+}
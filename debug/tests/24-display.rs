@@ -0,0 +1,101 @@
+// #[derive(CustomDisplay)] writes a Display impl from a template given on the
+// struct, or on each variant of an enum:
+//
+//     #[display("{name} listening on {addr}:{port}")]
+//
+// Placeholders name fields, or index them in tuple structs and variants, and
+// take the usual format specs. As with CustomDebug, the bounds on type
+// parameters are inferred from the fields that are actually referenced, using
+// the trait that each placeholder asks for.
+
+use derive_debug::CustomDisplay;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[derive(CustomDisplay)]
+#[display("{name} listening on {addr}:{port}")]
+pub struct Server {
+    name: &'static str,
+    addr: &'static str,
+    port: u16,
+}
+
+#[derive(CustomDisplay)]
+#[display("{0} of {1:>3} ({0:?})")]
+pub struct Progress<T>(T, u32);
+
+#[derive(CustomDisplay)]
+#[display("code {code:#06x}: {message}")]
+pub struct Status<C, M> {
+    code: C,
+    message: M,
+    marker: PhantomData<fn() -> NotDisplay>,
+}
+
+#[derive(Debug)]
+pub struct NotDisplay;
+
+pub trait Trait {
+    type Value;
+}
+
+#[derive(CustomDisplay)]
+#[display("value = {value}")]
+pub struct Projection<T: Trait> {
+    value: T::Value,
+}
+
+#[derive(CustomDisplay)]
+pub enum Error<T: Debug> {
+    #[display("not found: {path}")]
+    NotFound { path: String },
+    #[display("invalid value {0:?} at {1}")]
+    Invalid(T, usize),
+    #[display("timed out")]
+    Timeout,
+    #[display("{type} failed")]
+    Other { r#type: &'static str },
+}
+
+#[derive(CustomDisplay)]
+pub enum Never {}
+
+struct Unit;
+
+impl Trait for Unit {
+    type Value = bool;
+}
+
+fn assert_display<T: std::fmt::Display>() {}
+
+fn main() {
+    let server = Server {
+        name: "web",
+        addr: "127.0.0.1",
+        port: 8080,
+    };
+    assert_eq!(server.to_string(), "web listening on 127.0.0.1:8080");
+
+    assert_eq!(Progress("step", 7).to_string(), r#"step of   7 ("step")"#);
+
+    let status = Status {
+        code: 404u16,
+        message: "missing",
+        marker: PhantomData,
+    };
+    assert_eq!(status.to_string(), "code 0x0194: missing");
+
+    assert_eq!(Projection::<Unit> { value: true }.to_string(), "value = true");
+
+    let not_found = Error::<()>::NotFound {
+        path: "/etc".to_owned(),
+    };
+    assert_eq!(not_found.to_string(), "not found: /etc");
+    assert_eq!(Error::Invalid('x', 3).to_string(), "invalid value 'x' at 3");
+    assert_eq!(Error::<()>::Timeout.to_string(), "timed out");
+    assert_eq!(Error::<()>::Other { r#type: "io" }.to_string(), "io failed");
+
+    // `T` is only shown with `{:?}`, so it does not have to be Display.
+    assert_display::<Error<NotDisplay>>();
+    assert_display::<Never>();
+}
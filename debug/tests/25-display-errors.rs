@@ -0,0 +1,32 @@
+// Templates are checked when the derive expands: every placeholder has to name
+// a field of the struct or variant it is attached to, and every struct and
+// variant needs a template.
+
+use derive_debug::CustomDisplay;
+
+#[derive(CustomDisplay)]
+#[display("{name} on port {prot}")]
+pub struct Server {
+    name: String,
+    port: u16,
+}
+
+#[derive(CustomDisplay)]
+#[display("{2}")]
+pub struct Pair(u8, u8);
+
+#[derive(CustomDisplay)]
+pub struct Missing {
+    value: u8,
+}
+
+#[derive(CustomDisplay)]
+pub enum Error {
+    #[display("not found: {}")]
+    NotFound(String),
+    Timeout,
+    #[display(message)]
+    Other { message: String },
+}
+
+fn main() {}
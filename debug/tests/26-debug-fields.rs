@@ -0,0 +1,110 @@
+// With #[debug(fields)] the derive also implements derive_debug::DebugFields,
+// which hands each field to a callback as a name and a `&dyn Debug`, for
+// structured loggers that emit key=value pairs. The values are rendered the
+// same way as by the Debug impl: formats, skip, redact, skip_if and the
+// renderers all apply, and flattened fields are visited one by one. Structs
+// with named fields get the impl without the attribute, so they can be
+// flattened into a #[debug(fields)] type as they are.
+
+use derive_debug::{CustomDebug, DebugFields};
+use std::fmt::Debug;
+
+fn collect(value: &dyn DebugFields) -> Vec<String> {
+    let mut res = vec![];
+    value.debug_fields(&mut |name, value| res.push(format!("{}={:?}", name, value)));
+    res
+}
+
+#[derive(CustomDebug)]
+#[debug(fields)]
+pub struct Request<T> {
+    #[debug = "{:#x}"]
+    id: u32,
+    #[debug(redact)]
+    token: String,
+    #[debug(skip)]
+    cache: Vec<u8>,
+    #[debug(skip_if = "Option::is_none")]
+    parent: Option<u32>,
+    #[debug(flatten)]
+    peer: Peer<T>,
+}
+
+#[derive(CustomDebug)]
+#[debug(fields)]
+pub struct Peer<T> {
+    addr: T,
+    #[debug(len_only)]
+    buffer: Vec<u8>,
+}
+
+#[derive(CustomDebug)]
+#[debug(fields)]
+pub struct Session {
+    user: &'static str,
+    #[debug(flatten)]
+    limits: Limits,
+}
+
+#[derive(CustomDebug)]
+pub struct Limits {
+    #[debug = "{}/s"]
+    rate: u32,
+    #[debug(skip)]
+    burst: u32,
+}
+
+#[derive(CustomDebug)]
+#[debug(fields)]
+pub struct Point(i32, #[debug = "{}px"] i32);
+
+#[derive(CustomDebug)]
+#[debug(fields)]
+pub enum Event {
+    Connect { peer: &'static str },
+    Data(#[debug(limit = 2)] Vec<u8>),
+    Close,
+}
+
+fn main() {
+    let request = Request {
+        id: 255,
+        token: "secret".to_owned(),
+        cache: vec![],
+        parent: None,
+        peer: Peer {
+            addr: "10.0.0.1",
+            buffer: vec![0; 16],
+        },
+    };
+    assert_eq!(
+        collect(&request),
+        [
+            "id=0xff",
+            "token=[REDACTED]",
+            r#"addr="10.0.0.1""#,
+            "buffer=<len: 16>",
+        ],
+    );
+
+    let session = Session {
+        user: "root",
+        limits: Limits { rate: 10, burst: 20 },
+    };
+    assert_eq!(collect(&session), [r#"user="root""#, "rate=10/s"]);
+    assert_eq!(collect(&session.limits), ["rate=10/s"]);
+
+    assert_eq!(collect(&Point(1, 2)), ["0=1", "1=2px"]);
+
+    assert_eq!(
+        collect(&Event::Connect { peer: "a" }),
+        [r#"peer="a""#],
+    );
+    assert_eq!(collect(&Event::Data(vec![1, 2, 3])), ["0=[1, 2, … and 1 more]"]);
+    assert!(collect(&Event::Close).is_empty());
+
+    // The Debug impl is generated as usual.
+    assert_eq!(format!("{:?}", Point(1, 2)), "Point(1, 2px)");
+    fn assert_debug<T: Debug>() {}
+    assert_debug::<Request<u8>>();
+}
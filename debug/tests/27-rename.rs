@@ -0,0 +1,73 @@
+// Output names can differ from the Rust names, for readers who know the wire
+// format rather than the code:
+//
+//   #[debug(rename = "...")]     on a field or variant
+//   #[debug(name = "...")]       on a struct or union, replacing its name
+//   #[debug(rename_all = "...")] on a struct or variant for its fields, or on
+//                                an enum for its variants, using the same
+//                                case conventions as serde
+//
+// Raw identifiers are printed without their `r#` prefix.
+
+use derive_debug::{CustomDebug, DebugFields};
+
+#[derive(CustomDebug)]
+#[debug(name = "Request", rename_all = "camelCase", fields)]
+pub struct HttpRequest {
+    #[debug(rename = "Id")]
+    request_id: u32,
+    content_length: usize,
+    r#type: &'static str,
+}
+
+#[derive(CustomDebug)]
+#[debug(rename_all = "SCREAMING-KEBAB-CASE")]
+pub struct Headers {
+    content_type: &'static str,
+}
+
+#[derive(CustomDebug)]
+#[debug(rename_all = "snake_case")]
+pub enum Event {
+    ConnectionOpened,
+    #[debug(rename = "closed", rename_all = "PascalCase")]
+    ConnectionClosed { exit_code: i32, r#ref: u8 },
+    DataReceived(usize),
+}
+
+#[derive(CustomDebug)]
+#[debug(name = "Empty")]
+pub struct r#Unit;
+
+fn main() {
+    let request = HttpRequest {
+        request_id: 7,
+        content_length: 42,
+        r#type: "json",
+    };
+    assert_eq!(
+        format!("{:?}", request),
+        r#"Request { Id: 7, contentLength: 42, type: "json" }"#,
+    );
+
+    let mut names = vec![];
+    request.debug_fields(&mut |name, _| names.push(name));
+    assert_eq!(names, ["Id", "contentLength", "type"]);
+
+    let headers = Headers {
+        content_type: "text/plain",
+    };
+    assert_eq!(
+        format!("{:?}", headers),
+        r#"Headers { CONTENT-TYPE: "text/plain" }"#,
+    );
+
+    assert_eq!(format!("{:?}", Event::ConnectionOpened), "connection_opened");
+    assert_eq!(
+        format!("{:?}", Event::ConnectionClosed { exit_code: 1, r#ref: 2 }),
+        "closed { ExitCode: 1, Ref: 2 }",
+    );
+    assert_eq!(format!("{:?}", Event::DataReceived(3)), "data_received(3)");
+
+    assert_eq!(format!("{:?}", r#Unit), "Empty");
+}
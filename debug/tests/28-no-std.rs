@@ -0,0 +1,92 @@
+// The generated impls only refer to `::core` and to this crate, so they work in
+// `#![no_std]` crates. The `std` used by main below is only for checking the
+// output; the derives themselves cannot see it.
+
+#![no_std]
+
+use derive_debug::{CustomDebug, CustomDisplay, DebugFields};
+
+fn hidden(_: &u8, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.write_str("<hidden>")
+}
+
+#[derive(CustomDebug)]
+#[debug(fields)]
+pub struct Inner<T> {
+    value: T,
+    #[debug(len_only)]
+    list: &'static [u8],
+}
+
+#[derive(CustomDebug)]
+#[debug(alt_name = "Pretty")]
+pub struct Packet<'a, T> {
+    #[debug = "{:#x}"]
+    id: u32,
+    #[debug(hex, limit = 2)]
+    bytes: &'a [u8],
+    #[debug(limit = 1, alt = "{:?}")]
+    items: [T; 2],
+    #[debug(with = "hidden")]
+    secret: u8,
+    #[debug(redact)]
+    token: &'a str,
+    #[debug(skip_if = "Option::is_none")]
+    parent: Option<u8>,
+    #[debug(flatten)]
+    inner: Inner<T>,
+}
+
+#[derive(CustomDebug)]
+pub enum Event {
+    Ping(u8),
+    Close,
+}
+
+#[derive(CustomDisplay)]
+#[display("{name}: {code:x} {extra:?}")]
+pub struct Status<T> {
+    name: &'static str,
+    code: u8,
+    extra: T,
+}
+
+fn main() {
+    extern crate std;
+    use std::format;
+
+    let packet = Packet {
+        id: 10,
+        bytes: &[1, 2, 3],
+        items: [4, 5],
+        secret: 6,
+        token: "t",
+        parent: None,
+        inner: Inner {
+            value: 6,
+            list: &[0; 4],
+        },
+    };
+    assert_eq!(
+        format!("{:?}", packet),
+        concat!(
+            "Packet { id: 0xa, bytes: [01 02 … and 1 more bytes], ",
+            "items: [4, … and 1 more], secret: <hidden>, token: [REDACTED], ",
+            "value: 6, list: <len: 4> }",
+        ),
+    );
+    assert!(format!("{:#?}", packet).starts_with("Pretty {\n"));
+    assert_eq!(format!("{:?}", Event::Ping(1)), "Ping(1)");
+    assert_eq!(format!("{:?}", Event::Close), "Close");
+
+    let mut count = 0;
+    packet.inner.debug_fields(&mut |_, _| count += 1);
+    assert_eq!(count, 2);
+
+    let status = Status {
+        name: "ok",
+        code: 255,
+        extra: (),
+    };
+    assert_eq!(format!("{}", status), "ok: ff ()");
+}
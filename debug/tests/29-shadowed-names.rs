@@ -0,0 +1,69 @@
+// The generated impls use absolute paths into `::core`, so local modules named
+// `std` or `core`, and local items shadowing prelude names, do not affect them.
+
+#![allow(dead_code)]
+
+use derive_debug::{CustomDebug, CustomDisplay, DebugFields};
+
+mod std {}
+mod core {}
+
+struct Option;
+struct Some;
+struct None;
+struct Result;
+struct Formatter;
+trait AsRef {}
+trait FnMut {}
+trait Debug {}
+trait Display {}
+
+fn hidden(_: &u8, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+    f.write_str("<hidden>")
+}
+
+#[derive(CustomDebug)]
+#[debug(fields)]
+pub struct Packet<T> {
+    #[debug(hex, limit = 2)]
+    bytes: Vec<u8>,
+    #[debug(hex)]
+    magic: [u8; 2],
+    #[debug(with = "hidden", alt = "{}")]
+    secret: u8,
+    value: T,
+}
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Wrapper(u8);
+
+#[derive(CustomDebug)]
+pub union Union {
+    a: u8,
+}
+
+#[derive(CustomDisplay)]
+#[display("{value} ({value:?})")]
+pub struct Label<T> {
+    value: T,
+}
+
+fn main() {
+    let packet = Packet {
+        bytes: vec![1, 2, 3],
+        magic: [0xca, 0xfe],
+        secret: 7,
+        value: "v",
+    };
+    assert_eq!(
+        format!("{:?}", packet),
+        r#"Packet { bytes: [01 02 … and 1 more bytes], magic: [ca fe], secret: <hidden>, value: "v" }"#,
+    );
+    let mut count = 0;
+    packet.debug_fields(&mut |_, _| count += 1);
+    assert_eq!(count, 4);
+    assert_eq!(format!("{:?}", Wrapper(1)), "1");
+    assert_eq!(format!("{:?}", Union { a: 0 }), "Union { .. }");
+    assert_eq!(format!("{}", Label { value: "x" }), r#"x ("x")"#);
+}
@@ -0,0 +1,117 @@
+// Const generic parameters and lifetimes never need a Debug bound, and a type
+// parameter is found wherever it appears inside arrays, slices and references
+// of any length, so the where-clause holds exactly the bounds that the fields
+// need.
+
+use derive_debug::{CustomDebug, CustomDisplay};
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[derive(CustomDebug)]
+pub struct Buf<'a, T, const N: usize> {
+    data: &'a [T; N],
+    #[debug(hex)]
+    raw: [u8; N],
+}
+
+#[derive(CustomDebug)]
+pub struct Matrix<T, const R: usize, const C: usize = 2>([[T; C]; R]);
+
+#[derive(CustomDebug)]
+pub struct Counted<T, const N: usize> {
+    #[debug(len_only)]
+    items: [u8; N],
+    marker: PhantomData<[T; N]>,
+}
+
+#[derive(CustomDebug)]
+pub struct Window<'a, T, const N: usize> {
+    #[debug(limit = 2)]
+    chunks: Vec<[T; N]>,
+    #[debug(flatten)]
+    buf: Buf<'a, T, N>,
+}
+
+#[derive(CustomDebug)]
+pub struct Borrowed<'a, 'b: 'a> {
+    name: &'a str,
+    bytes: &'b [u8],
+    text: Cow<'a, str>,
+}
+
+#[derive(CustomDebug)]
+pub struct Tagged<const TAG: char>;
+
+#[derive(CustomDebug)]
+pub enum Slot<'a, T, const N: usize> {
+    Full(&'a [T; N]),
+    Empty,
+}
+
+#[derive(CustomDisplay)]
+#[display("{first} of {len}")]
+pub struct Head<'a, T, const N: usize> {
+    first: &'a T,
+    len: usize,
+    rest: [PhantomData<T>; N],
+}
+
+pub struct NotDebug;
+
+fn assert_debug<T: Debug>() {}
+
+fn main() {
+    let data = [1, 2, 3];
+    let buf = Buf {
+        data: &data,
+        raw: [0xab; 3],
+    };
+    assert_eq!(format!("{:?}", buf), "Buf { data: [1, 2, 3], raw: [ab ab ab] }");
+
+    let matrix: Matrix<u8, 2> = Matrix([[1, 2], [3, 4]]);
+    assert_eq!(format!("{:?}", matrix), "Matrix([[1, 2], [3, 4]])");
+
+    let counted = Counted::<NotDebug, 4> {
+        items: [0; 4],
+        marker: PhantomData,
+    };
+    assert!(format!("{:?}", counted).starts_with("Counted { items: <len: 4>, marker: PhantomData<"));
+
+    let window = Window {
+        chunks: vec![[1, 2], [3, 4], [5, 6]],
+        buf: Buf {
+            data: &[7, 8],
+            raw: [0; 2],
+        },
+    };
+    assert_eq!(
+        format!("{:?}", window),
+        "Window { chunks: [[1, 2], [3, 4], … and 1 more], data: [7, 8], raw: [00 00] }",
+    );
+
+    let borrowed = Borrowed {
+        name: "n",
+        bytes: b"b",
+        text: Cow::Borrowed("t"),
+    };
+    assert_eq!(
+        format!("{:?}", borrowed),
+        r#"Borrowed { name: "n", bytes: [98], text: "t" }"#,
+    );
+
+    assert_eq!(format!("{:?}", Tagged::<'x'>), "Tagged");
+    assert_eq!(format!("{:?}", Slot::Full(&data)), "Full([1, 2, 3])");
+
+    let head = Head {
+        first: &1,
+        len: 3,
+        rest: [PhantomData::<i32>; 2],
+    };
+    assert_eq!(head.to_string(), "1 of 3");
+
+    // Only the parameters that the printed fields use are bounded.
+    assert_debug::<Counted<NotDebug, 0>>();
+    assert_debug::<Tagged<'y'>>();
+    assert_debug::<Slot<'static, u8, 1>>();
+}
@@ -9,4 +9,26 @@ fn tests() {
     t.pass("tests/06-bound-trouble.rs");
     t.pass("tests/07-associated-type.rs");
     t.pass("tests/08-escape-hatch.rs");
+    t.pass("tests/09-enum.rs");
+    t.pass("tests/10-tuple-unit-union.rs");
+    t.pass("tests/11-skip-redact.rs");
+    t.pass("tests/12-debug-with.rs");
+    t.pass("tests/13-bound-where-clause.rs");
+    t.compile_fail("tests/14-bound-error.rs");
+    t.pass("tests/15-bound-inference.rs");
+    t.pass("tests/16-phantom-wrappers.rs");
+    t.pass("tests/17-format-siblings.rs");
+    t.compile_fail("tests/18-format-errors.rs");
+    t.compile_fail("tests/19-attribute-errors.rs");
+    t.pass("tests/20-transparent-flatten.rs");
+    t.pass("tests/21-skip-if.rs");
+    t.pass("tests/22-renderers.rs");
+    t.pass("tests/23-alternate.rs");
+    t.pass("tests/24-display.rs");
+    t.compile_fail("tests/25-display-errors.rs");
+    t.pass("tests/26-debug-fields.rs");
+    t.pass("tests/27-rename.rs");
+    t.pass("tests/28-no-std.rs");
+    t.pass("tests/29-shadowed-names.rs");
+    t.pass("tests/30-const-generics.rs");
 }
@@ -1,8 +1,845 @@
 use proc_macro::TokenStream;
+use proc_macro2::TokenTree;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
 
 #[proc_macro]
 pub fn seq(input: TokenStream) -> TokenStream {
-    let _ = input;
+    let seq = syn::parse_macro_input!(input as Seq);
+    seq.expand().into()
+}
+
+// `R in 0..4, C in 0..4 { ... }`: one or more ranges, iterated as their
+// cartesian product with the first one outermost.
+struct Seq {
+    ranges: Vec<Range>,
+    body: proc_macro2::TokenStream,
+}
+
+// `N in 0..8` or `N in 0..=7`, optionally counting down as `N in 7..=0 rev`
+// or `N in (0..8).rev()`, and optionally followed by `step 2`.
+struct Range {
+    var: syn::Ident,
+    values: Vec<u64>,
+    format: LitFormat,
+}
+
+// How the values of a range are written: with the radix, zero padding and
+// suffix of its bounds, so that `0x00..0x10` counts `0x00, 0x01, ..., 0x0F`
+// and `0u8..4u8` counts `0u8, 1u8, 2u8, 3u8`.
+struct LitFormat {
+    radix: u32,
+    width: usize,
+    upper: bool,
+    suffix: String,
+}
+
+mod kw {
+    syn::custom_keyword!(rev);
+    syn::custom_keyword!(step);
+}
+
+// The value of each variable in scope.
+type Bindings<'a> = Vec<(&'a Range, u64)>;
+
+impl Parse for Seq {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut ranges: Vec<Range> = vec![];
+        loop {
+            let range: Range = input.parse()?;
+            if ranges.iter().any(|item| item.var == range.var) {
+                return Err(syn::Error::new_spanned(
+                    &range.var,
+                    format!("duplicate variable `{}`", range.var),
+                ));
+            }
+            ranges.push(range);
+            if !input.peek(syn::Token![,]) {
+                break;
+            }
+            input.parse::<syn::Token![,]>()?;
+        }
+        let content;
+        syn::braced!(content in input);
+        Ok(Seq {
+            ranges,
+            body: content.parse()?,
+        })
+    }
+}
+
+impl Parse for Range {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let var = input.parse()?;
+        input.parse::<syn::Token![in]>()?;
+        let (bounds, mut rev) = if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            let bounds: Bounds = content.parse()?;
+            input.parse::<syn::Token![.]>()?;
+            input.parse::<kw::rev>()?;
+            let args;
+            syn::parenthesized!(args in input);
+            if !args.is_empty() {
+                return Err(args.error("`rev` takes no arguments"));
+            }
+            (bounds, true)
+        } else {
+            (input.parse::<Bounds>()?, false)
+        };
+        let mut descending = false;
+        let mut step = None;
+        loop {
+            if input.peek(kw::rev) {
+                let keyword = input.parse::<kw::rev>()?;
+                if rev || descending {
+                    return Err(syn::Error::new_spanned(keyword, "duplicate `rev`"));
+                }
+                descending = true;
+                rev = true;
+            } else if input.peek(kw::step) {
+                let keyword = input.parse::<kw::step>()?;
+                if step.is_some() {
+                    return Err(syn::Error::new_spanned(keyword, "duplicate `step`"));
+                }
+                let lit: syn::LitInt = input.parse()?;
+                let value: usize = lit.base10_parse()?;
+                if value == 0 {
+                    return Err(syn::Error::new_spanned(lit, "`step` must be at least 1"));
+                }
+                step = Some(value);
+            } else {
+                break;
+            }
+        }
+        Ok(Range {
+            var,
+            values: bounds.values(descending, rev, step.unwrap_or(1))?,
+            format: bounds.format()?,
+        })
+    }
+}
+
+// `a..b` or `a..=b`. Bounds passed in as `$n:literal` by macro_rules arrive
+// wrapped in a group with no delimiters, which `LitInt` looks through.
+struct Bounds {
+    start: syn::LitInt,
+    end: syn::LitInt,
+    inclusive: bool,
+}
+
+impl Parse for Bounds {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let start = input.parse()?;
+        let inclusive = if input.peek(syn::Token![..=]) {
+            input.parse::<syn::Token![..=]>()?;
+            true
+        } else {
+            input.parse::<syn::Token![..]>()?;
+            false
+        };
+        Ok(Bounds {
+            start,
+            end: input.parse()?,
+            inclusive,
+        })
+    }
+}
+
+impl Bounds {
+    // The values in iteration order. A `descending` range is written from its
+    // high end to its low end; `rev` iterates from the high end either way.
+    fn values(&self, descending: bool, rev: bool, step: usize) -> syn::Result<Vec<u64>> {
+        let start: u64 = self.start.base10_parse()?;
+        let end: u64 = self.end.base10_parse()?;
+        let (low, high) = if descending {
+            (end, start)
+        } else {
+            (start, end)
+        };
+        if low > high {
+            let dots = if self.inclusive { "..=" } else { ".." };
+            let tokens: proc_macro2::TokenStream = [
+                TokenTree::Literal(self.start.token()),
+                TokenTree::Literal(self.end.token()),
+            ]
+            .into_iter()
+            .collect();
+            let message = if descending {
+                format!("`{}{}{} rev` counts up, remove `rev`", start, dots, end)
+            } else {
+                format!("`{}{}{}` counts down, add `rev`", start, dots, end)
+            };
+            return Err(syn::Error::new_spanned(tokens, message));
+        }
+        // The end that is written second is the one excluded by `..`. Only the
+        // values stepped on are generated, so wide stepped ranges are cheap.
+        let (low, high) = match (self.inclusive, descending) {
+            (true, _) => (low, high),
+            (false, false) if low == high => return Ok(vec![]),
+            (false, false) => (low, high - 1),
+            (false, true) if low == high => return Ok(vec![]),
+            (false, true) => (low + 1, high),
+        };
+        Ok(if rev {
+            (low..=high).rev().step_by(step).collect()
+        } else {
+            (low..=high).step_by(step).collect()
+        })
+    }
+
+    // The format of the start, with the suffix of whichever bound has one.
+    fn format(&self) -> syn::Result<LitFormat> {
+        let mut format = LitFormat::new(&self.start);
+        match (self.start.suffix(), self.end.suffix()) {
+            ("", suffix) => format.suffix = suffix.to_string(),
+            (start, end) if !end.is_empty() && start != end => {
+                return Err(syn::Error::new_spanned(
+                    &self.end,
+                    format!(
+                        "expected suffix `{}` to match the start of the range",
+                        start
+                    ),
+                ))
+            }
+            _ => {}
+        }
+        Ok(format)
+    }
+}
+
+impl LitFormat {
+    fn new(lit: &syn::LitInt) -> Self {
+        let repr = lit.to_string();
+        let digits = repr[..repr.len() - lit.suffix().len()].replace('_', "");
+        let (radix, digits) = match digits.get(..2) {
+            Some("0x") => (16, &digits[2..]),
+            Some("0o") => (8, &digits[2..]),
+            Some("0b") => (2, &digits[2..]),
+            _ => (10, &digits[..]),
+        };
+        LitFormat {
+            radix,
+            width: digits.len(),
+            upper: !digits.chars().any(|c| c.is_ascii_lowercase()),
+            suffix: lit.suffix().to_string(),
+        }
+    }
+
+    // The format of a paste such as `~N:02` or `~{N:x}`: an optional zero-padded
+    // width followed by an optional radix of `x`, `X`, `o` or `b`.
+    fn from_spec(spec: &str) -> Option<Self> {
+        let split = spec.find(|c: char| !c.is_ascii_digit());
+        let (width, radix) = spec.split_at(split.unwrap_or(spec.len()));
+        if !width.is_empty() && (!width.starts_with('0') || width.len() < 2) {
+            return None;
+        }
+        let (radix, upper) = match radix {
+            "" if !width.is_empty() => (10, false),
+            "x" => (16, false),
+            "X" => (16, true),
+            "o" => (8, false),
+            "b" => (2, false),
+            _ => return None,
+        };
+        Some(LitFormat {
+            radix,
+            width: width.parse().unwrap_or(0),
+            upper,
+            suffix: String::new(),
+        })
+    }
+
+    fn digits(&self, n: u64) -> String {
+        let width = self.width;
+        match self.radix {
+            16 if self.upper => format!("{:0width$X}", n),
+            16 => format!("{:0width$x}", n),
+            8 => format!("{:0width$o}", n),
+            2 => format!("{:0width$b}", n),
+            _ => format!("{:0width$}", n),
+        }
+    }
+
+    fn literal(&self, n: u64, span: proc_macro2::Span) -> proc_macro2::Literal {
+        let prefix = match self.radix {
+            16 => "0x",
+            8 => "0o",
+            2 => "0b",
+            _ => "",
+        };
+        let repr = format!("{}{}{}", prefix, self.digits(n), self.suffix);
+        syn::LitInt::new(&repr, span).token()
+    }
+}
+
+// Every combination of values of `ranges`, each added to `bound`.
+fn product<'a>(bound: &Bindings<'a>, ranges: &[&'a Range]) -> Vec<Bindings<'a>> {
+    let mut res = vec![bound.clone()];
+    for range in ranges {
+        let values = &range.values;
+        res = res
+            .into_iter()
+            .flat_map(|bindings| {
+                values.iter().map(move |n| {
+                    let mut bindings = bindings.clone();
+                    bindings.push((*range, *n));
+                    bindings
+                })
+            })
+            .collect();
+    }
+    res
+}
+
+struct Expanded {
+    stream: proc_macro2::TokenStream,
+    // Whether a `#( ... )*` section was found.
+    sections: bool,
+    // Whether an `#[if ...]` guard turned out false, which drops the
+    // iteration it is in.
+    skip: bool,
+    // Errors in guards, which drop their iteration too but are kept.
+    errors: proc_macro2::TokenStream,
+}
+
+// `#( ... )*`, `#R( ... )*` or either with a separator before the `*`, as in
+// `#( ... ),*`.
+struct Section<'a> {
+    ranges: Vec<&'a Range>,
+    group: &'a proc_macro2::Group,
+    separator: &'a [TokenTree],
+    // Its length in tokens.
+    len: usize,
+}
+
+impl Expanded {
+    // The tokens, or only the errors if the iteration is dropped.
+    fn output(self) -> proc_macro2::TokenStream {
+        if self.skip {
+            self.errors
+        } else {
+            self.stream
+        }
+    }
+}
+
+impl Seq {
+    // With `#( ... )*` sections in the body only those are repeated, otherwise
+    // the whole body is.
+    fn expand(&self) -> proc_macro2::TokenStream {
+        let expanded = self.expand_tokens(&self.body, &vec![], true);
+        if expanded.sections {
+            return expanded.output();
+        }
+        let ranges: Vec<&Range> = self.ranges.iter().collect();
+        product(&vec![], &ranges)
+            .iter()
+            .map(|bound| self.expand_tokens(&self.body, bound, true).output())
+            .collect()
+    }
+
+    fn get_range(&self, var: &syn::Ident) -> Option<&Range> {
+        self.ranges.iter().find(|item| item.var == *var)
+    }
+
+    // The section at the start of `tokens`, if any. `#R( ... )*` iterates
+    // over R, `#( ... )*` over all of the variables not bound yet.
+    fn get_section<'a>(&'a self, tokens: &'a [TokenTree], bound: &Bindings) -> Option<Section<'a>> {
+        let (ranges, rest) = match tokens {
+            [TokenTree::Punct(pound), TokenTree::Ident(var), rest @ ..]
+                if pound.as_char() == '#' =>
+            {
+                (vec![self.get_range(var)?], rest)
+            }
+            [TokenTree::Punct(pound), rest @ ..] if pound.as_char() == '#' => {
+                let ranges = self
+                    .ranges
+                    .iter()
+                    .filter(|range| get_binding(&range.var, bound).is_none())
+                    .collect();
+                (ranges, rest)
+            }
+            _ => return None,
+        };
+        let (group, rest) = match rest {
+            [TokenTree::Group(group), rest @ ..]
+                if group.delimiter() == proc_macro2::Delimiter::Parenthesis =>
+            {
+                (group, rest)
+            }
+            _ => return None,
+        };
+        let separator = &rest[..get_separator_len(rest)?];
+        Some(Section {
+            ranges,
+            group,
+            separator,
+            len: tokens.len() - rest.len() + separator.len() + 1,
+        })
+    }
+
+    fn expand_section(&self, section: &Section, bound: &Bindings) -> proc_macro2::TokenStream {
+        let mut res = proc_macro2::TokenStream::new();
+        let mut first = true;
+        for bindings in product(bound, &section.ranges) {
+            let expanded = self.expand_tokens(&section.group.stream(), &bindings, true);
+            // A single error stands for the whole section, as it would not
+            // parse with errors in between its iterations.
+            if !expanded.errors.is_empty() {
+                return expanded.errors;
+            }
+            if expanded.skip {
+                continue;
+            }
+            if !first {
+                res.extend(copy_separator(section.separator));
+            }
+            first = false;
+            res.extend(expanded.stream);
+        }
+        res
+    }
+
+    // Substitutes the bound variables in `tokens` and, if `sections` is set,
+    // expands the sections and guards in it. Sections and guards in the body
+    // of a nested `seq!` are left to that invocation, which still sees the
+    // values of our variables.
+    fn expand_tokens(
+        &self,
+        tokens: &proc_macro2::TokenStream,
+        bound: &Bindings,
+        sections: bool,
+    ) -> Expanded {
+        let tokens: Vec<TokenTree> = tokens.clone().into_iter().collect();
+        let mut res = Expanded {
+            stream: proc_macro2::TokenStream::new(),
+            sections: false,
+            skip: false,
+            errors: proc_macro2::TokenStream::new(),
+        };
+        let mut i = 0;
+        while i < tokens.len() {
+            if sections {
+                if let Some(section) = self.get_section(&tokens[i..], bound) {
+                    res.stream.extend(self.expand_section(&section, bound));
+                    res.sections = true;
+                    i += section.len;
+                    continue;
+                }
+                if let Some(guard) = get_guard(&tokens[i..]) {
+                    match guard.and_then(|guard| check_condition(&guard, bound)) {
+                        Ok(keep) => res.skip |= !keep,
+                        Err(e) => {
+                            res.skip = true;
+                            res.errors.extend(e.to_compile_error());
+                        }
+                    }
+                    i += 2;
+                    continue;
+                }
+            }
+            if let Some(value) = get_value_expr(&tokens[i..], bound) {
+                match value {
+                    Ok(lit) => res.stream.extend([TokenTree::Literal(lit)]),
+                    Err(e) => res.stream.extend(e.to_compile_error()),
+                }
+                i += 2;
+                continue;
+            }
+            match &tokens[i] {
+                TokenTree::Ident(prefix) if get_paste(&tokens[i + 1..], bound).is_some() => {
+                    let mut ident: syn::Result<String> = Ok(prefix.unraw().to_string());
+                    i += 1;
+                    while let Some((text, len)) = get_paste(&tokens[i..], bound) {
+                        ident = ident.and_then(|ident| Ok(ident + &text?));
+                        i += len;
+                    }
+                    match ident {
+                        Ok(ident) => res
+                            .stream
+                            .extend([TokenTree::Ident(syn::Ident::new(&ident, prefix.span()))]),
+                        Err(e) => res.stream.extend(e.to_compile_error()),
+                    }
+                    continue;
+                }
+                TokenTree::Ident(ident) => match get_binding(ident, bound) {
+                    Some((range, n)) => {
+                        let lit = range.format.literal(n, ident.span());
+                        res.stream.extend([TokenTree::Literal(lit)]);
+                    }
+                    None => res.stream.extend([tokens[i].clone()]),
+                },
+                TokenTree::Group(group) => {
+                    let nested = sections && is_nested_seq(&tokens[..i]);
+                    let expanded = self.expand_tokens(&group.stream(), bound, sections && !nested);
+                    res.sections |= expanded.sections;
+                    res.skip |= expanded.skip;
+                    res.errors.extend(expanded.errors);
+                    res.stream
+                        .extend([TokenTree::Group(copy_group(group, expanded.stream))]);
+                }
+                token => res.stream.extend([token.clone()]),
+            }
+            i += 1;
+        }
+        res
+    }
+}
+
+// The length of the separator in front of the `*` that ends a section: none,
+// a single token as in `#( ... ),*` or an operator as in `#( ... )=>*`.
+fn get_separator_len(tokens: &[TokenTree]) -> Option<usize> {
+    let is_star =
+        |token: &TokenTree| matches!(token, TokenTree::Punct(star) if star.as_char() == '*');
+    match tokens {
+        [TokenTree::Ident(_) | TokenTree::Literal(_), star, ..] if is_star(star) => Some(1),
+        _ => {
+            let len = tokens
+                .iter()
+                .position(|token| !matches!(token, TokenTree::Punct(_)) || is_star(token))?;
+            if is_star(&tokens[len]) {
+                Some(len)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+// The separator tokens, ending with a lone punct so that they are not glued
+// to the start of the next iteration.
+fn copy_separator(separator: &[TokenTree]) -> Vec<TokenTree> {
+    let mut res = separator.to_vec();
+    if let Some(TokenTree::Punct(punct)) = res.last_mut() {
+        let mut alone = proc_macro2::Punct::new(punct.as_char(), proc_macro2::Spacing::Alone);
+        alone.set_span(punct.span());
+        *punct = alone;
+    }
+    res
+}
+
+// The condition of an `#[if N != 0]` guard at the start of `tokens`.
+fn get_guard(tokens: &[TokenTree]) -> Option<syn::Result<syn::Expr>> {
+    match tokens {
+        [TokenTree::Punct(pound), TokenTree::Group(group), ..]
+            if pound.as_char() == '#' && group.delimiter() == proc_macro2::Delimiter::Bracket =>
+        {
+            let stream: Vec<TokenTree> = group.stream().into_iter().collect();
+            match stream.split_first() {
+                Some((TokenTree::Ident(kw), [])) if kw == "if" => Some(Err(
+                    syn::Error::new_spanned(kw, "expected a condition after `if`"),
+                )),
+                Some((TokenTree::Ident(kw), rest)) if kw == "if" => {
+                    let rest: proc_macro2::TokenStream = rest.iter().cloned().collect();
+                    Some(
+                        syn::parse2(rest.clone())
+                            .map_err(|_| syn::Error::new_spanned(rest, EXPECTED_COMPARISON)),
+                    )
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+const EXPECTED_COMPARISON: &str =
+    "expected a comparison of integers and variables in scope, like `N % 2 == 0`";
+
+fn get_binding<'a>(ident: &syn::Ident, bound: &Bindings<'a>) -> Option<(&'a Range, u64)> {
+    bound.iter().find(|(range, _)| range.var == *ident).copied()
+}
+
+// The text pasted by `~N` or `~(N + 1)`, optionally followed by a numeric
+// format as in `~N:02`, or by `~{N:x}`, if `tokens` starts with one over bound
+// variables, and the number of tokens it takes. The value is pasted in decimal
+// unless a format is given. A format made of letters only has to be braced,
+// as `arg~N: X` is a type annotation.
+fn get_paste(tokens: &[TokenTree], bound: &Bindings) -> Option<(syn::Result<String>, usize)> {
+    let n = match tokens {
+        [TokenTree::Punct(tilde), TokenTree::Ident(ident), ..] if tilde.as_char() == '~' => {
+            Ok(get_binding(ident, bound)?.1)
+        }
+        [TokenTree::Punct(tilde), TokenTree::Group(group), ..]
+            if tilde.as_char() == '~'
+                && group.delimiter() == proc_macro2::Delimiter::Parenthesis =>
+        {
+            get_expr_value(group.stream(), bound)?
+        }
+        [TokenTree::Punct(tilde), TokenTree::Group(group), ..]
+            if tilde.as_char() == '~' && group.delimiter() == proc_macro2::Delimiter::Brace =>
+        {
+            return Some((get_braced_paste(group, bound)?, 2));
+        }
+        _ => return None,
+    };
+    // A spec is a literal such as `02` or `02x`, or one of the radixes `x`, `o`
+    // and `b`. `X` is taken for a type, as in `arg~N: X`, and needs braces.
+    if let [TokenTree::Punct(colon), spec, ..] = &tokens[2..] {
+        let spec = match spec {
+            TokenTree::Literal(spec) => spec.to_string(),
+            TokenTree::Ident(spec) if spec == "x" || spec == "o" || spec == "b" => spec.to_string(),
+            _ => String::new(),
+        };
+        if colon.as_char() == ':' && colon.spacing() == proc_macro2::Spacing::Alone {
+            if let Some(format) = LitFormat::from_spec(&spec) {
+                return Some((n.map(|n| format.digits(n)), 4));
+            }
+        }
+    }
+    Some((n.map(|n| n.to_string()), 2))
+}
+
+// The text of `~{N:x}` or `~{N + 1:02x}`.
+fn get_braced_paste(group: &proc_macro2::Group, bound: &Bindings) -> Option<syn::Result<String>> {
+    let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+    let (expr, spec) = match tokens.as_slice() {
+        [expr @ .., TokenTree::Punct(colon), spec] if colon.as_char() == ':' => (expr, spec),
+        _ => return None,
+    };
+    let format = LitFormat::from_spec(&spec.to_string())?;
+    let n = get_expr_value(expr.iter().cloned().collect(), bound)?;
+    Some(n.map(|n| format.digits(n)))
+}
+
+// The literal for `#[N * 4 + 0x100]` if `tokens` starts with one, written like
+// the values of the first variable in it. Attributes such as `#[derive(Debug)]`
+// or `#[doc = "..."]` are not arithmetic and are left alone.
+fn get_value_expr(
+    tokens: &[TokenTree],
+    bound: &Bindings,
+) -> Option<syn::Result<proc_macro2::Literal>> {
+    let group = match tokens {
+        [TokenTree::Punct(pound), TokenTree::Group(group), ..]
+            if pound.as_char() == '#' && group.delimiter() == proc_macro2::Delimiter::Bracket =>
+        {
+            group
+        }
+        _ => return None,
+    };
+    let expr: syn::Expr = syn::parse2(group.stream()).ok()?;
+    if !is_arithmetic(&expr, bound) {
+        return None;
+    }
+    let range = get_first_range(&expr, bound);
+    // A suffixed value is computed in the type of its suffix.
+    let ty = match range {
+        Some(range) if !range.format.suffix.is_empty() => &range.format.suffix,
+        _ => "u64",
+    };
+    Some(eval(&expr, bound, ty).map(|n| match range {
+        Some(range) => range.format.literal(n, group.span()),
+        None => {
+            let mut lit = proc_macro2::Literal::u64_unsuffixed(n);
+            lit.set_span(group.span());
+            lit
+        }
+    }))
+}
+
+// The range of the first variable in an arithmetic expression, whose format
+// the value of the expression is written in.
+fn get_first_range<'a>(expr: &syn::Expr, bound: &Bindings<'a>) -> Option<&'a Range> {
+    match expr {
+        syn::Expr::Path(syn::ExprPath { path, .. }) => {
+            Some(get_binding(path.get_ident()?, bound)?.0)
+        }
+        syn::Expr::Paren(syn::ExprParen { expr, .. }) => get_first_range(expr, bound),
+        syn::Expr::Binary(syn::ExprBinary { left, right, .. }) => {
+            get_first_range(left, bound).or_else(|| get_first_range(right, bound))
+        }
+        _ => None,
+    }
+}
+
+fn get_expr_value(tokens: proc_macro2::TokenStream, bound: &Bindings) -> Option<syn::Result<u64>> {
+    let expr: syn::Expr = syn::parse2(tokens).ok()?;
+    if !is_arithmetic(&expr, bound) {
+        return None;
+    }
+    Some(eval(&expr, bound, "u64"))
+}
+
+// Whether `expr` is made of integer literals, bound variables, parentheses
+// and the integer operators only.
+fn is_arithmetic(expr: &syn::Expr, bound: &Bindings) -> bool {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(_),
+            ..
+        }) => true,
+        syn::Expr::Path(syn::ExprPath {
+            path, qself: None, ..
+        }) => path
+            .get_ident()
+            .is_some_and(|ident| get_binding(ident, bound).is_some()),
+        syn::Expr::Paren(syn::ExprParen { expr, .. }) => is_arithmetic(expr, bound),
+        syn::Expr::Binary(syn::ExprBinary {
+            left, op, right, ..
+        }) => {
+            get_operator(op).is_some() && is_arithmetic(left, bound) && is_arithmetic(right, bound)
+        }
+        _ => false,
+    }
+}
+
+type Operator = fn(u64, u64) -> Option<u64>;
+
+fn get_operator(op: &syn::BinOp) -> Option<(&'static str, Operator)> {
+    Some(match op {
+        syn::BinOp::Add(_) => ("+", u64::checked_add),
+        syn::BinOp::Sub(_) => ("-", u64::checked_sub),
+        syn::BinOp::Mul(_) => ("*", u64::checked_mul),
+        syn::BinOp::Div(_) => ("/", u64::checked_div),
+        syn::BinOp::Rem(_) => ("%", u64::checked_rem),
+        syn::BinOp::Shl(_) => ("<<", |a, b| a.checked_shl(b.try_into().ok()?)),
+        syn::BinOp::Shr(_) => (">>", |a, b| a.checked_shr(b.try_into().ok()?)),
+        syn::BinOp::BitAnd(_) => ("&", |a, b| Some(a & b)),
+        syn::BinOp::BitOr(_) => ("|", |a, b| Some(a | b)),
+        syn::BinOp::BitXor(_) => ("^", |a, b| Some(a ^ b)),
+        _ => return None,
+    })
+}
+
+// Evaluates an expression accepted by `is_arithmetic`. Results that do not
+// fit in a u64, such as `N - 1` for `N == 0`, and division by zero are an
+// error.
+// The value of an arithmetic expression, each operation of which has to stay
+// within the integer type `ty`.
+fn eval(expr: &syn::Expr, bound: &Bindings, ty: &str) -> syn::Result<u64> {
+    let max = get_int_max(ty);
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => match lit.base10_parse()? {
+            n if n > max => Err(syn::Error::new_spanned(
+                lit,
+                format!("`{}` is out of range for {}", n, ty),
+            )),
+            n => Ok(n),
+        },
+        syn::Expr::Path(syn::ExprPath { path, .. }) => {
+            let ident = path.get_ident().unwrap();
+            Ok(get_binding(ident, bound).unwrap().1)
+        }
+        syn::Expr::Paren(syn::ExprParen { expr, .. }) => eval(expr, bound, ty),
+        syn::Expr::Binary(syn::ExprBinary {
+            left, op, right, ..
+        }) => {
+            let (a, b) = (eval(left, bound, ty)?, eval(right, bound, ty)?);
+            let (symbol, operator) = get_operator(op).unwrap();
+            operator(a, b).filter(|n| *n <= max).ok_or_else(|| {
+                let problem = match symbol {
+                    "/" | "%" if b == 0 => "divides by zero".to_string(),
+                    _ => format!("is out of range for {}", ty),
+                };
+                syn::Error::new_spanned(expr, format!("`{} {} {}` {}", a, symbol, b, problem))
+            })
+        }
+        _ => unreachable!(),
+    }
+}
+
+// The largest value of the integer type named by a literal suffix. `usize` and
+// `isize` are taken to be 64 bits wide; the compiler rejects literals that are
+// too large for a narrower target.
+fn get_int_max(ty: &str) -> u64 {
+    match ty {
+        "u8" => u8::MAX.into(),
+        "i8" => i8::MAX as u64,
+        "u16" => u16::MAX.into(),
+        "i16" => i16::MAX as u64,
+        "u32" => u32::MAX.into(),
+        "i32" => i32::MAX as u64,
+        "i64" | "isize" => i64::MAX as u64,
+        _ => u64::MAX,
+    }
+}
+
+// Whether `tokens` ends with `seq!`, so that the group after it is the input
+// of a nested invocation.
+fn is_nested_seq(tokens: &[TokenTree]) -> bool {
+    match tokens {
+        [.., TokenTree::Ident(ident), TokenTree::Punct(bang)] => {
+            ident == "seq" && bang.as_char() == '!'
+        }
+        _ => false,
+    }
+}
+
+fn copy_group(group: &proc_macro2::Group, stream: proc_macro2::TokenStream) -> proc_macro2::Group {
+    let mut res = proc_macro2::Group::new(group.delimiter(), stream);
+    res.set_span(group.span());
+    res
+}
+
+// Whether `expr` compares arithmetic expressions, possibly combined with
+// `&&`, `||` and `!`.
+fn is_condition(expr: &syn::Expr, bound: &Bindings) -> bool {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Bool(_),
+            ..
+        }) => true,
+        syn::Expr::Paren(syn::ExprParen { expr, .. }) => is_condition(expr, bound),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Not(_),
+            expr,
+            ..
+        }) => is_condition(expr, bound),
+        syn::Expr::Binary(syn::ExprBinary {
+            left, op, right, ..
+        }) => match op {
+            syn::BinOp::And(_) | syn::BinOp::Or(_) => {
+                is_condition(left, bound) && is_condition(right, bound)
+            }
+            syn::BinOp::Eq(_)
+            | syn::BinOp::Ne(_)
+            | syn::BinOp::Lt(_)
+            | syn::BinOp::Le(_)
+            | syn::BinOp::Gt(_)
+            | syn::BinOp::Ge(_) => is_arithmetic(left, bound) && is_arithmetic(right, bound),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn check_condition(expr: &syn::Expr, bound: &Bindings) -> syn::Result<bool> {
+    if !is_condition(expr, bound) {
+        return Err(syn::Error::new_spanned(expr, EXPECTED_COMPARISON));
+    }
+    eval_condition(expr, bound)
+}
 
-    unimplemented!()
+// Evaluates an expression accepted by `is_condition`.
+fn eval_condition(expr: &syn::Expr, bound: &Bindings) -> syn::Result<bool> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Bool(lit),
+            ..
+        }) => Ok(lit.value),
+        syn::Expr::Paren(syn::ExprParen { expr, .. }) => eval_condition(expr, bound),
+        syn::Expr::Unary(syn::ExprUnary { expr, .. }) => Ok(!eval_condition(expr, bound)?),
+        syn::Expr::Binary(syn::ExprBinary {
+            left, op, right, ..
+        }) => Ok(match op {
+            syn::BinOp::And(_) => eval_condition(left, bound)? && eval_condition(right, bound)?,
+            syn::BinOp::Or(_) => eval_condition(left, bound)? || eval_condition(right, bound)?,
+            _ => {
+                let (a, b) = (eval(left, bound, "u64")?, eval(right, bound, "u64")?);
+                match op {
+                    syn::BinOp::Eq(_) => a == b,
+                    syn::BinOp::Ne(_) => a != b,
+                    syn::BinOp::Lt(_) => a < b,
+                    syn::BinOp::Le(_) => a <= b,
+                    syn::BinOp::Gt(_) => a > b,
+                    _ => a >= b,
+                }
+            }
+        }),
+        _ => unreachable!(),
+    }
 }
@@ -0,0 +1,55 @@
+// Several variables can be declared, separated by commas. The body, or each
+// `#( ... )*` section, is repeated for every combination of their values with
+// the first variable varying slowest. A section written `#R( ... )*` repeats
+// only over R, so that sections for different variables can be nested, and
+// identifiers can paste several variables in a row.
+
+use seq::seq;
+
+seq!(R in 0..3, C in 0..2 {
+    #[derive(Default)]
+    pub struct Matrix {
+        #(
+            m~R~C: u32,
+        )*
+    }
+
+    impl Matrix {
+        #(
+            pub fn get~R~C(&self) -> u32 {
+                self.m~R~C
+            }
+        )*
+
+        #R(
+            pub fn row~R(&self) -> [u32; 2] {
+                [#C(self.m~R~C,)*]
+            }
+        )*
+
+        pub fn set_diagonal(&mut self) {
+            #R(
+                #C(
+                    self.m~R~C = (R == C) as u32;
+                )*
+            )*
+        }
+    }
+});
+
+seq!(A in 0..2, B in 1..=2 {
+    const PAIR~A~B: (u8, u8) = (A, B);
+});
+
+fn main() {
+    let mut matrix = Matrix::default();
+    matrix.set_diagonal();
+    assert_eq!(matrix.get00(), 1);
+    assert_eq!(matrix.get21(), 0);
+    assert_eq!(matrix.row0(), [1, 0]);
+    assert_eq!(matrix.row1(), [0, 1]);
+    assert_eq!(matrix.row2(), [0, 0]);
+
+    assert_eq!(PAIR01, (0, 1));
+    assert_eq!(PAIR12, (1, 2));
+}
@@ -0,0 +1,33 @@
+// A seq! inside the body of another sees the values of the outer variables,
+// both in its header and in its body, and its own `#( ... )*` sections are
+// left for it to expand. Identifiers can be pasted from variables of both.
+
+use seq::seq;
+
+seq!(N in 1..4 {
+    fn sum~N() -> u64 {
+        seq!(I in 0..N {
+            0 #(+ I * 10 + N)*
+        })
+    }
+
+    seq!(I in 0..2 {
+        const OFFSET_~N~I: u64 = N * 100 + I;
+    });
+});
+
+seq!(R in 0..2 {
+    seq!(C in 0..3 {
+        const CELL_~R~C: u64 = R * 10 + C;
+    });
+});
+
+fn main() {
+    assert_eq!(sum1(), 1);
+    assert_eq!(sum2(), (0 + 2) + (10 + 2));
+    assert_eq!(sum3(), (0 + 3) + (10 + 3) + (20 + 3));
+    assert_eq!(OFFSET_10, 100);
+    assert_eq!(OFFSET_31, 301);
+    assert_eq!(CELL_00, 0);
+    assert_eq!(CELL_12, 12);
+}
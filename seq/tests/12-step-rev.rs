@@ -0,0 +1,43 @@
+// A range can be followed by `step S` to take every S-th value, and can count
+// down either as `(a..b).rev()`, which is `a..b` backwards, or as `b..=a rev`
+// written from its high end. An empty range repeats nothing at all. Only the
+// values stepped on are generated, however wide the range.
+
+use seq::seq;
+
+const OFFSETS: [u32; 8] = seq!(N in 0..64 step 8 { [#(N,)*] });
+const DOWN: [u32; 4] = seq!(N in (0..4).rev() { [#(N,)*] });
+const DOWN_INCLUSIVE: [u32; 4] = seq!(N in 3..=0 rev { [#(N,)*] });
+const DOWN_EXCLUSIVE: [u32; 3] = seq!(N in 3..0 rev { [#(N,)*] });
+const DOWN_BY_TWO: [u32; 4] = seq!(N in (0..=7).rev() step 2 { [#(N,)*] });
+const DOWN_BY_THREE: [u32; 3] = seq!(N in 8..=0 rev step 3 { [#(N,)*] });
+const EMPTY: [u32; 0] = seq!(N in 5..5 { [#(N,)*] });
+const EMPTY_REV: [u32; 0] = seq!(N in (2..2).rev() { [#(N,)*] });
+
+seq!(N in 0..32 step 8 {
+    pub const REG~N: u32 = 0x100 + N;
+});
+
+seq!(N in 0x0000_0000..0x4000_0000 step 0x1000_0000 {
+    pub const REGION~N: u64 = N;
+});
+
+const WIDE_DOWN: [u64; 2] = seq!(N in (0..=0xffff_ffff).rev() step 0x8000_0000 { [#(N,)*] });
+
+seq!(N in 0..0 {
+    compile_error!("an empty range expands to nothing");
+});
+
+fn main() {
+    assert_eq!(REGION268435456 + REGION805306368, 0x4000_0000);
+    assert_eq!(WIDE_DOWN, [0xffff_ffff, 0x7fff_ffff]);
+    assert_eq!(OFFSETS, [0, 8, 16, 24, 32, 40, 48, 56]);
+    assert_eq!(DOWN, [3, 2, 1, 0]);
+    assert_eq!(DOWN_INCLUSIVE, [3, 2, 1, 0]);
+    assert_eq!(DOWN_EXCLUSIVE, [3, 2, 1]);
+    assert_eq!(DOWN_BY_TWO, [7, 5, 3, 1]);
+    assert_eq!(DOWN_BY_THREE, [8, 5, 2]);
+    assert_eq!(EMPTY, []);
+    assert_eq!(EMPTY_REV, []);
+    assert_eq!([REG0, REG8, REG16, REG24], [0x100, 0x108, 0x110, 0x118]);
+}
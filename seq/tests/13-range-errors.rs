@@ -0,0 +1,23 @@
+// A range whose bounds are the wrong way round is an error rather than empty,
+// as it is almost always a missing or extra `rev`. Bounds with different
+// suffixes are an error too.
+
+use seq::seq;
+
+seq!(N in 16..0 {
+    const A~N: u8 = N;
+});
+
+seq!(N in 0..=16 rev {
+    const B~N: u8 = N;
+});
+
+seq!(N in 0..16 step 0 {
+    const C~N: u8 = N;
+});
+
+seq!(N in 0u8..16u16 {
+    const D~N: u8 = N;
+});
+
+fn main() {}
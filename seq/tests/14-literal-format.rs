@@ -0,0 +1,31 @@
+// Substituted values are written the way the range's bounds are: with the same
+// suffix, so that `0u8..4u8` gives `0u8, 1u8, 2u8, 3u8`, and with the same
+// radix and zero padding, so that `0x00..0x10` gives `0x00, ..., 0x0F`. A
+// suffix on only one bound applies to both. Pasted idents stay decimal.
+
+use seq::seq;
+
+const SUFFIXED: [&str; 4] = seq!(N in 0u8..4u8 { [#(stringify!(N),)*] });
+const END_SUFFIX: [&str; 3] = seq!(N in 1..=3i64 { [#(stringify!(N),)*] });
+const HEX: [&str; 3] = seq!(N in 0x0E..0x11 { [#(stringify!(N),)*] });
+const LOWER_HEX: [&str; 2] = seq!(N in 0x0a..0x0c { [#(stringify!(N),)*] });
+const BIN: [&str; 3] = seq!(N in 0b000..0b110 step 2 { [#(stringify!(N),)*] });
+const OCTAL: [&str; 2] = seq!(N in 0o6..0o10 { [#(stringify!(N),)*] });
+const PADDED: [&str; 3] = seq!(N in 08..11 { [#(stringify!(N),)*] });
+const SEPARATED: [&str; 2] = seq!(N in 0x_ff_u16..0x101 { [#(stringify!(N),)*] });
+
+seq!(N in 0x0E..0x10 {
+    pub const IRQ~N: u16 = N;
+});
+
+fn main() {
+    assert_eq!(SUFFIXED, ["0u8", "1u8", "2u8", "3u8"]);
+    assert_eq!(END_SUFFIX, ["1i64", "2i64", "3i64"]);
+    assert_eq!(HEX, ["0x0E", "0x0F", "0x10"]);
+    assert_eq!(LOWER_HEX, ["0x0a", "0x0b"]);
+    assert_eq!(BIN, ["0b000", "0b010", "0b100"]);
+    assert_eq!(OCTAL, ["0o6", "0o7"]);
+    assert_eq!(PADDED, ["08", "09", "10"]);
+    assert_eq!(SEPARATED, ["0xffu16", "0x100u16"]);
+    assert_eq!(IRQ14 + IRQ15, 29);
+}
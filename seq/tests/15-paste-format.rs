@@ -0,0 +1,69 @@
+// A paste can carry a format like those of `format!`, as in `Reg~N:02` for
+// `Reg00, Reg01, ..., Reg15`: a zero-padded width, a radix of `x`, `X`, `o` or
+// `b`, or both, as in `~N:02x`. Padding keeps generated names in the order
+// they sort in. The one spec written in braces is a bare `X`, as in
+// `PORT_~{N:X}`, because `~N: X` is a type annotation, as in `arg~N: X`.
+
+use seq::seq;
+
+seq!(N in 0..16 {
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    enum Reg {
+        #(
+            Reg~N:02,
+        )*
+    }
+});
+
+seq!(N in 8..12 {
+    #(
+        fn irq_~N:x() -> u32 {
+            N
+        }
+    )*
+});
+
+seq!(N in 250..252 {
+    #(
+        const PORT_~{N:X}: u32 = N;
+        const MASK_~N:012b: u32 = N;
+        const MODE_~N:03o: u32 = N;
+        const BITS_~N:b: u32 = N;
+        const OCT_~N:o: u32 = N;
+        const PAGE_~N:04X~N: u32 = N;
+    )*
+});
+
+seq!(N in 0..2 {
+    struct Block {
+        #(
+            reg~N: u32,
+        )*
+    }
+});
+
+seq!(N in 0..2 {
+    fn pair<X>(#(arg~N: X,)*) -> [X; 2] {
+        [#(arg~N,)*]
+    }
+});
+
+fn main() {
+    assert_eq!(Reg::Reg00 as u8, 0);
+    assert_eq!(Reg::Reg09 as u8, 9);
+    assert_eq!(Reg::Reg15 as u8, 15);
+
+    assert_eq!(irq_8() + irq_9() + irq_a() + irq_b(), 38);
+
+    assert_eq!(PORT_FA + PORT_FB, 501);
+    assert_eq!(MASK_000011111010, 250);
+    assert_eq!(MODE_373 + MODE_372, 501);
+    assert_eq!(PAGE_00FA250, 250);
+    assert_eq!(BITS_11111010 + BITS_11111011, 501);
+    assert_eq!(OCT_372 + OCT_373, 501);
+
+    assert_eq!(pair("a", "b"), ["a", "b"]);
+
+    let block = Block { reg0: 1, reg1: 2 };
+    assert_eq!(block.reg0 + block.reg1, 3);
+}
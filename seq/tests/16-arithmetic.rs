@@ -0,0 +1,78 @@
+// Arithmetic over the variables is evaluated at expansion time:
+//
+//   #[N * 4 + 0x100]   an integer literal, usable in patterns and array lengths
+//   ~(N + 1)           pasted into an identifier, as in `State~(N + 1)`
+//
+// with integer literals, parentheses and the integer operators. The literal
+// is written with the suffix, radix and padding of the first variable in the
+// expression, like the variable itself. Attributes such as `#[derive(Debug)]`
+// are left as they are, and `~(N + 1)` takes a format like `~N` does.
+
+use seq::seq;
+
+seq!(N in 0..4 {
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    enum State {
+        #(
+            State~N = #[N * 4 + 0x100],
+        )*
+    }
+
+    impl State {
+        fn offset(self) -> u32 {
+            match self as u32 {
+                #(
+                    #[N * 4 + 0x100] => #[N * 4],
+                )*
+                _ => unreachable!(),
+            }
+        }
+    }
+});
+
+seq!(N in 0..3 {
+    #(
+        struct Node~N {
+            data: [u8; #[1 << N]],
+            next: Option<Box<Node~(N + 1)>>,
+        }
+    )*
+});
+
+struct Node3;
+
+seq!(N in 1..3 {
+    #(
+        const SLOT~(N - 1):02~(N * 2 % 3): usize = #[(N + 1) / 2 | 8 ^ 2 >> 1];
+    )*
+});
+
+seq!(R in 0..2, C in 0..2 {
+    const INDICES: [usize; 4] = [#(#[R * 2 + C],)*];
+});
+
+const WRAPPED: [u8; 2] = seq!(N in 253u8..=254u8 { [#(#[N + 1].wrapping_add(1),)*] });
+const OFFSETS: [&str; 2] = seq!(N in 0x00..0x02 { [#(stringify!(#[N * 0x10 + 0xA0]),)*] });
+const CONSTANT: &str = seq!(N in 0u8..1u8 { stringify!(#[2 + 2]) });
+
+fn main() {
+    assert_eq!(State::State3 as u32, 0x10c);
+    assert_eq!(State::State2.offset(), 8);
+
+    let node = Node0 {
+        data: [0; 1],
+        next: Some(Box::new(Node1 {
+            data: [0; 2],
+            next: None,
+        })),
+    };
+    assert_eq!(node.data.len() + node.next.unwrap().data.len(), 3);
+    let _: Option<Box<Node3>> = Node2 { data: [0; 4], next: None }.next;
+
+    assert_eq!(SLOT002, 9);
+    assert_eq!(SLOT011, 9);
+    assert_eq!(INDICES, [0, 1, 2, 3]);
+    assert_eq!(WRAPPED, [255, 0]);
+    assert_eq!(OFFSETS, ["0xA0", "0xB0"]);
+    assert_eq!(CONSTANT, "4");
+}
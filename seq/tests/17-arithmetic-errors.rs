@@ -0,0 +1,23 @@
+// A value that does not fit in a u64, or in the type of the range suffix for
+// `#[...]` values, or a division by zero, is an error at the operation
+// producing it.
+
+use seq::seq;
+
+seq!(N in 0..2 {
+    #(
+        struct Prev~N(Option<Prev~(N - 1)>);
+    )*
+});
+
+seq!(N in 1..2 {
+    const A: u64 = #[N << 64];
+    const B: u64 = #[18446744073709551615 + N];
+    const C: u64 = #[N / (N - 1)];
+});
+
+seq!(N in 0u8..4u8 {
+    const D: [u32; 4] = [#(#[N * 100] as u32,)*];
+});
+
+fn main() {}
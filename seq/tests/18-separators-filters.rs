@@ -0,0 +1,70 @@
+// Like in macro_rules, a section can have a separator between its iterations,
+// such as `#( ... ),*`, a single token or an operator like `=>`, so that no
+// trailing separator is left behind. An `#[if N != 0]` guard anywhere in the
+// repeated tokens skips the iterations its condition is false for; conditions
+// compare arithmetic over the variables, combined with `&&`, `||` and `!`. An
+// ordinary `if` after a section is left alone.
+
+use seq::seq;
+
+seq!(N in 0..3 {
+    type Tuple = (#(A~N),*);
+
+    #(
+        struct A~N;
+    )*
+
+    fn sum(#(a~N: u32),*) -> u32 {
+        0 #(+ a~N)*
+    }
+});
+
+const EVEN: [u32; 4] = seq!(N in 0..8 { [#(#[if N % 2 == 0] N),*] });
+const SUM: u32 = seq!(N in 1..5 { #(N)+* });
+const MASK: u32 = seq!(N in 0..4 { #(#[if N != 2] 1 << N)|* });
+const NONE: [u32; 0] = seq!(N in 0..4 { [#(#[if false] N),*] });
+
+seq!(N in 0..8 {
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    enum Irq {
+        #(
+            #[if N != 3 && !(N >= 5 && N < 7)]
+            Irq~N = N,
+        )*
+    }
+});
+
+seq!(N in 0..4 {
+    #[if N % 2 == 1]
+    const ODD~N: u32 = N;
+});
+
+seq!(R in 0..3, C in 0..3 {
+    const UPPER: [(u32, u32); 3] = [#(#[if R < C] (R, C)),*];
+});
+
+seq!(N in 1..3 {
+    fn run(cond: bool) -> u32 {
+        let mut x = 0;
+        #( x += N; )* if cond { x += 100; }
+        x
+    }
+});
+
+fn main() {
+    let _: Tuple = (A0, A1, A2);
+    assert_eq!(sum(1, 2, 3), 6);
+    assert_eq!(run(true), 103);
+    assert_eq!(run(false), 3);
+
+    assert_eq!(EVEN, [0, 2, 4, 6]);
+    assert_eq!(SUM, 10);
+    assert_eq!(MASK, 0b1011);
+    assert_eq!(NONE, []);
+
+    assert_eq!(Irq::Irq4 as u32, 4);
+    assert_eq!(Irq::Irq7 as u32, 7);
+
+    assert_eq!(ODD1 + ODD3, 4);
+    assert_eq!(UPPER, [(0, 1), (0, 2), (1, 2)]);
+}
@@ -0,0 +1,21 @@
+// Conditions can only compare integers and the variables in scope.
+
+use seq::seq;
+
+seq!(N in 0..4 {
+    const A: [u32; 2] = [#(#[if N.is_power_of_two()] N),*];
+});
+
+seq!(N in 0..4 {
+    #(
+        #[if M > 2]
+        const B~N: u32 = N;
+    )*
+});
+
+seq!(N in 0..4 {
+    #[if]
+    const D~N: u32 = N;
+});
+
+fn main() {}
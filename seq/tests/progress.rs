@@ -1,13 +1,23 @@
 #[test]
 fn tests() {
     let t = trybuild::TestCases::new();
-    //t.pass("tests/01-parse-header.rs");
-    //t.pass("tests/02-parse-body.rs");
-    //t.compile_fail("tests/03-expand-four-errors.rs");
-    //t.pass("tests/04-paste-ident.rs");
-    //t.pass("tests/05-repeat-section.rs");
-    //t.pass("tests/06-init-array.rs");
-    //t.pass("tests/07-inclusive-range.rs");
-    //t.compile_fail("tests/08-ident-span.rs");
-    //t.pass("tests/09-interaction-with-macrorules.rs");
+    t.pass("tests/01-parse-header.rs");
+    t.pass("tests/02-parse-body.rs");
+    t.compile_fail("tests/03-expand-four-errors.rs");
+    t.pass("tests/04-paste-ident.rs");
+    t.pass("tests/05-repeat-section.rs");
+    t.pass("tests/06-init-array.rs");
+    t.pass("tests/07-inclusive-range.rs");
+    t.compile_fail("tests/08-ident-span.rs");
+    t.pass("tests/09-interaction-with-macrorules.rs");
+    t.pass("tests/10-cartesian-product.rs");
+    t.pass("tests/11-nested-seq.rs");
+    t.pass("tests/12-step-rev.rs");
+    t.compile_fail("tests/13-range-errors.rs");
+    t.pass("tests/14-literal-format.rs");
+    t.pass("tests/15-paste-format.rs");
+    t.pass("tests/16-arithmetic.rs");
+    t.compile_fail("tests/17-arithmetic-errors.rs");
+    t.pass("tests/18-separators-filters.rs");
+    t.compile_fail("tests/19-condition-errors.rs");
 }